@@ -0,0 +1,111 @@
+#ifndef SMS_CLIENT_H
+#define SMS_CLIENT_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The call succeeded.
+ */
+#define SMS_OK 0
+
+/**
+ * A required argument was null, or a string was not valid UTF-8.
+ */
+#define SMS_ERR_INVALID_ARGUMENT -1
+
+/**
+ * The client returned an error, see `sms_client_last_error`.
+ */
+#define SMS_ERR_CLIENT -2
+
+/**
+ * The default most events queued for `sms_client_poll_event`.
+ */
+#define EVENT_QUEUE_DEFAULT_CAPACITY 1024
+
+/**
+ * An opaque client handle, owning the client and the runtime that drives it.
+ */
+typedef struct SmsClient SmsClient;
+
+/**
+ * Create a client. `http_url` is required, `ws_url` and `authorization` may be null.
+ * Returns null on failure. The handle must be freed with `sms_client_free`.
+ *
+ * # Safety
+ * Every non-null argument must be a valid NUL-terminated string.
+ */
+struct SmsClient *sms_client_new(const char *http_url,
+                                 const char *ws_url,
+                                 const char *authorization);
+
+/**
+ * Free a client handle, stopping its WebSocket connection. Null is ignored.
+ *
+ * # Safety
+ * `client` must be null or a handle from `sms_client_new` that has not been freed.
+ */
+void sms_client_free(struct SmsClient *client);
+
+/**
+ * Send an SMS message. On success the server message ID is written to `message_id`,
+ * if it is not null.
+ *
+ * # Safety
+ * `client` must be a live handle, `to` and `content` valid NUL-terminated strings,
+ * and `message_id` null or valid for writes.
+ */
+int sms_client_send_sms(const struct SmsClient *client,
+                        const char *to,
+                        const char *content,
+                        int64_t *message_id);
+
+/**
+ * Start the WebSocket connection in the background, queueing every received event
+ * as a JSON string to be read with `sms_client_poll_event`.
+ *
+ * # Safety
+ * `client` must be a live handle.
+ */
+int sms_client_start_events(const struct SmsClient *client);
+
+/**
+ * Set the most queued events (at least 1), and whether a full queue drops the newest
+ * event instead of the oldest. Already queued events over the new capacity are dropped.
+ *
+ * # Safety
+ * `client` must be a live handle.
+ */
+int sms_client_set_event_queue(const struct SmsClient *client,
+                               uintptr_t capacity,
+                               bool drop_newest);
+
+/**
+ * Take the oldest queued event as a JSON string, or null if there are none.
+ * The string must be freed with `sms_client_string_free`.
+ *
+ * # Safety
+ * `client` must be a live handle.
+ */
+char *sms_client_poll_event(const struct SmsClient *client);
+
+/**
+ * Free a string returned by this library. Null is ignored.
+ *
+ * # Safety
+ * `value` must be null or a string from `sms_client_poll_event` that has not been freed.
+ */
+void sms_client_string_free(char *value);
+
+/**
+ * Get a description of the last error on the calling thread, or null if there was none.
+ * The string is owned by the library and valid until the next failing call on this thread.
+ */
+const char *sms_client_last_error(void);
+
+#endif  /* SMS_CLIENT_H */
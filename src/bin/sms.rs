@@ -0,0 +1,100 @@
+//! A small command line tool for debugging against a live SMS-API instance.
+//!
+//! Connection settings are read from the environment:
+//! - `SMS_HTTP_URL`: HTTP base URL, eg: <http://192.168.1.2:3000> (required)
+//! - `SMS_WS_URL`: WebSocket URL, eg: <ws://192.168.1.2:3000/ws> (required for `tail`)
+//! - `SMS_AUTH`: Optional authorization token.
+//! - `SMS_CERT`: Optional path to a TLS certificate to trust.
+
+#![warn(clippy::all, clippy::pedantic)]
+
+use sms_client::Client;
+use sms_client::config::{ClientConfig, HttpConfig, WebSocketConfig};
+use sms_client::types::http::HttpPaginationOptions;
+use sms_client::types::sms::SmsOutgoingMessage;
+
+const USAGE: &str = "Usage: sms <command>
+
+Commands:
+  send <to> <message...>       Send an SMS message
+  messages <number> [limit]    List messages sent to and from a number (default limit 20)
+  tail                         Print WebSocket events as they are received
+  status                       Print the modem and network status";
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&args).await {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: &[String]) -> CliResult<()> {
+    let Some((command, args)) = args.split_first() else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+
+    match (command.as_str(), args) {
+        ("send", [to, message @ ..]) if !message.is_empty() => {
+            let client = client_from_env()?;
+            let message = SmsOutgoingMessage::simple_message(to, &message.join(" "));
+            let response = client.http()?.send_sms(&message).await?;
+            println!("{response:#?}");
+        }
+        ("messages", [number, rest @ ..]) if rest.len() <= 1 => {
+            let limit = match rest.first() {
+                Some(limit) => limit.parse()?,
+                None => 20,
+            };
+
+            let client = client_from_env()?;
+            let pagination = HttpPaginationOptions::default().with_limit(limit);
+            for message in client
+                .http()?
+                .get_messages(number, Some(pagination))
+                .await?
+            {
+                println!("{message:#?}");
+            }
+        }
+        ("tail", []) => {
+            let client = client_from_env()?;
+            client.on_message(|event| println!("{event:#?}")).await?;
+            client.start_blocking_websocket().await?;
+        }
+        ("status", []) => {
+            let client = client_from_env()?;
+            let http = client.http()?;
+            println!("Version: {}", http.get_version().await?);
+            println!("Network: {:#?}", http.get_network_status().await?);
+            println!("Signal: {:#?}", http.get_signal_strength().await?);
+            println!("Operator: {:#?}", http.get_network_operator().await?);
+            println!("Battery: {:#?}", http.get_battery_level().await?);
+        }
+        _ => return Err(USAGE.into()),
+    }
+    Ok(())
+}
+
+/// Build a client from the `SMS_*` environment variables.
+fn client_from_env() -> CliResult<Client> {
+    let http_url = std::env::var("SMS_HTTP_URL").map_err(|_| "SMS_HTTP_URL is not set")?;
+    let ws_url = std::env::var("SMS_WS_URL").ok();
+
+    let mut config = ClientConfig::from_parts(
+        Some(HttpConfig::new(http_url)),
+        ws_url.map(WebSocketConfig::new),
+    );
+    if let Ok(token) = std::env::var("SMS_AUTH") {
+        config = config.with_auth(token);
+    }
+    if let Ok(certificate) = std::env::var("SMS_CERT") {
+        config = config.with_certificate(certificate)?;
+    }
+
+    Ok(Client::new(config)?)
+}
@@ -0,0 +1,244 @@
+//! A synchronous HTTP client for scripts and CLI tools that don't use an async runtime.
+//! Each call drives the async `HttpClient` on an internal single-threaded runtime, so these
+//! methods must not be called from within an async context.
+//!
+//! # Example
+//! ```
+//! use sms_client::blocking::Client;
+//! use sms_client::config::ClientConfig;
+//! use sms_client::types::sms::SmsOutgoingMessage;
+//!
+//! fn notify(url: &str) -> sms_client::error::ClientResult<()> {
+//!     let client = Client::new(ClientConfig::http_only(url))?;
+//!     let message = SmsOutgoingMessage::simple_message("+447700900000", "Backup finished");
+//!
+//!     let response = client.send_sms(&message)?;
+//!     println!("Sent message {}", response.message_id);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::http::capabilities::ServerCapabilities;
+use crate::http::error::HttpResult;
+use crate::http::meta::WithMeta;
+use crate::http::mms::{HttpOutgoingMmsMessage, MmsSendResult};
+use crate::http::modem_cache::ModemRefreshFailure;
+use crate::types::modem::{ModemRestartResponse, SimPinStatus};
+use sms_types::gnss::{FixStatus, PositionReport};
+use sms_types::http::{
+    HttpModemBatteryLevelResponse, HttpModemNetworkOperatorResponse,
+    HttpModemNetworkStatusResponse, HttpModemSignalStrengthResponse, HttpPaginationOptions,
+    HttpSmsDeviceInfoData, HttpSmsSendResponse, LatestNumberFriendlyNamePair,
+};
+use sms_types::sms::{SmsDeliveryReport, SmsMessage, SmsOutgoingMessage};
+
+/// A synchronous SMS-API HTTP client.
+#[derive(Debug)]
+pub struct Client {
+    http: crate::http::HttpClient,
+    runtime: tokio::runtime::Runtime,
+}
+impl Client {
+    /// Create a blocking client from a config, which must include HTTP configuration.
+    /// Any WebSocket configuration is ignored.
+    pub fn new(config: crate::config::ClientConfig) -> crate::error::ClientResult<Self> {
+        let http_config = config
+            .http
+            .ok_or(crate::error::ClientError::ConfigError("HttpConfig"))?;
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        // Create the HTTP client within the runtime, as reqwest may register with its reactor.
+        let mut http = {
+            let _guard = runtime.enter();
+            crate::http::HttpClient::new(http_config, config.tls.as_ref())?
+        };
+        if let Some(dump) = config.debug_dump {
+            http.set_debug_dump(crate::dump::DebugDump::open(dump)?);
+        }
+
+        Ok(Self { http, runtime })
+    }
+
+    /// Get the underlying async HTTP client, eg: for its synchronous helpers like
+    /// `send_sms_dry_run` and the last-known state cache.
+    #[must_use]
+    pub fn http(&self) -> &crate::http::HttpClient {
+        &self.http
+    }
+
+    /// Set/Remove the friendly name for a given phone number.
+    pub fn set_friendly_name(
+        &self,
+        phone_number: impl Into<String>,
+        friendly_name: Option<impl Into<String>>,
+    ) -> HttpResult<bool> {
+        self.runtime
+            .block_on(self.http.set_friendly_name(phone_number, friendly_name))
+    }
+
+    /// Get the friendly name associated with a given phone number.
+    pub fn get_friendly_name(&self, phone_number: impl Into<String>) -> HttpResult<Option<String>> {
+        self.runtime
+            .block_on(self.http.get_friendly_name(phone_number))
+    }
+
+    /// Get messages sent to and from a given phone number. Pagination options are supported.
+    pub fn get_messages(
+        &self,
+        phone_number: impl Into<String>,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> HttpResult<Vec<SmsMessage>> {
+        self.runtime
+            .block_on(self.http.get_messages(phone_number, pagination))
+    }
+
+    /// Get the latest phone numbers that have been in contact with the SMS-API.
+    /// Pagination options are supported.
+    pub fn get_latest_numbers(
+        &self,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> HttpResult<Vec<LatestNumberFriendlyNamePair>> {
+        self.runtime
+            .block_on(self.http.get_latest_numbers(pagination))
+    }
+
+    /// Get received delivery reports for a given `message_id`. Pagination options are supported.
+    pub fn get_delivery_reports(
+        &self,
+        message_id: i64,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> HttpResult<Vec<SmsDeliveryReport>> {
+        self.runtime
+            .block_on(self.http.get_delivery_reports(message_id, pagination))
+    }
+
+    /// Send an SMS message, returning the message reference and message id.
+    pub fn send_sms(&self, message: &SmsOutgoingMessage) -> HttpResult<HttpSmsSendResponse> {
+        self.runtime.block_on(self.http.send_sms(message))
+    }
+
+    /// Send an SMS message, also returning selected response headers.
+    pub fn send_sms_with_meta(
+        &self,
+        message: &SmsOutgoingMessage,
+    ) -> HttpResult<WithMeta<HttpSmsSendResponse>> {
+        self.runtime.block_on(self.http.send_sms_with_meta(message))
+    }
+
+    /// Send an MMS message if the server supports it, otherwise send its fallback SMS.
+    pub fn send_mms(
+        &self,
+        message: &HttpOutgoingMmsMessage,
+        capabilities: &ServerCapabilities,
+    ) -> HttpResult<MmsSendResult> {
+        self.runtime
+            .block_on(self.http.send_mms(message, capabilities))
+    }
+
+    /// Send a batch of SMS messages, returning a result for each message in the same order.
+    pub fn send_sms_batch(
+        &self,
+        messages: &[SmsOutgoingMessage],
+    ) -> Vec<HttpResult<HttpSmsSendResponse>> {
+        self.runtime.block_on(self.http.send_sms_batch(messages))
+    }
+
+    /// Get the carrier network status.
+    pub fn get_network_status(&self) -> HttpResult<HttpModemNetworkStatusResponse> {
+        self.runtime.block_on(self.http.get_network_status())
+    }
+
+    /// Get the modem signal strength for the connected tower.
+    pub fn get_signal_strength(&self) -> HttpResult<HttpModemSignalStrengthResponse> {
+        self.runtime.block_on(self.http.get_signal_strength())
+    }
+
+    /// Get the underlying network operator.
+    pub fn get_network_operator(&self) -> HttpResult<HttpModemNetworkOperatorResponse> {
+        self.runtime.block_on(self.http.get_network_operator())
+    }
+
+    /// Get the SIM service provider.
+    pub fn get_service_provider(&self) -> HttpResult<String> {
+        self.runtime.block_on(self.http.get_service_provider())
+    }
+
+    /// Get the modem battery level.
+    pub fn get_battery_level(&self) -> HttpResult<HttpModemBatteryLevelResponse> {
+        self.runtime.block_on(self.http.get_battery_level())
+    }
+
+    /// Get the GNSS fix status.
+    pub fn get_gnss_status(&self) -> HttpResult<FixStatus> {
+        self.runtime.block_on(self.http.get_gnss_status())
+    }
+
+    /// Get the GNSS location.
+    pub fn get_gnss_location(&self) -> HttpResult<PositionReport> {
+        self.runtime.block_on(self.http.get_gnss_location())
+    }
+
+    /// Power on the GNSS receiver. Returns true if the modem acknowledged the change.
+    pub fn enable_gnss(&self) -> HttpResult<bool> {
+        self.runtime.block_on(self.http.enable_gnss())
+    }
+
+    /// Power off the GNSS receiver. Returns true if the modem acknowledged the change.
+    pub fn disable_gnss(&self) -> HttpResult<bool> {
+        self.runtime.block_on(self.http.disable_gnss())
+    }
+
+    /// Set how often the GNSS receiver sends unsolicited position reports.
+    pub fn set_gnss_report_interval(&self, interval: std::time::Duration) -> HttpResult<bool> {
+        self.runtime
+            .block_on(self.http.set_gnss_report_interval(interval))
+    }
+
+    /// Get the SIM PIN status.
+    pub fn get_pin_status(&self) -> HttpResult<SimPinStatus> {
+        self.runtime.block_on(self.http.get_pin_status())
+    }
+
+    /// Unlock the SIM with its PIN. Returns true if the modem accepted the PIN.
+    pub fn unlock_sim(&self, pin: impl Into<String>) -> HttpResult<bool> {
+        self.runtime.block_on(self.http.unlock_sim(pin))
+    }
+
+    /// Supply the configured SIM PIN if the SIM is waiting for one, returning the resulting status.
+    pub fn ensure_sim_unlocked(&self) -> HttpResult<SimPinStatus> {
+        self.runtime.block_on(self.http.ensure_sim_unlocked())
+    }
+
+    /// Restart the modem, eg: to recover it from a wedged state.
+    pub fn restart_modem(&self) -> HttpResult<ModemRestartResponse> {
+        self.runtime.block_on(self.http.restart_modem())
+    }
+
+    /// Get device info summary.
+    pub fn get_device_info(&self) -> HttpResult<HttpSmsDeviceInfoData> {
+        self.runtime.block_on(self.http.get_device_info())
+    }
+
+    /// Get the configured sender SMS number.
+    pub fn get_phone_number(&self) -> HttpResult<Option<String>> {
+        self.runtime.block_on(self.http.get_phone_number())
+    }
+
+    /// Get the SMS-API build version.
+    pub fn get_version(&self) -> HttpResult<String> {
+        self.runtime.block_on(self.http.get_version())
+    }
+
+    /// Refetch every modem response if the modem cache is enabled, returning the routes that failed.
+    pub fn force_refresh(&self) -> Vec<ModemRefreshFailure> {
+        self.runtime.block_on(self.http.force_refresh())
+    }
+
+    /// Get the server capabilities, parsed from the feature names in its version string.
+    pub fn get_capabilities(&self) -> HttpResult<ServerCapabilities> {
+        self.runtime.block_on(self.http.get_capabilities())
+    }
+}
@@ -2,7 +2,7 @@
 
 /// HTTP-specific configuration.
 #[cfg(feature = "http")]
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct HttpConfig {
     /// HTTP base URL. eg: <http://192.168.1.2:3000>
     pub url: String,
@@ -19,6 +19,59 @@ pub struct HttpConfig {
     /// (requests that must send and receive modem data). This should
     /// be higher than the default timeout as they can take longer.
     pub modem_timeout: Option<std::time::Duration>,
+
+    /// Optional client-side rate limit, shared by all requests from the client.
+    pub rate_limit: Option<HttpRateLimit>,
+
+    /// Optional circuit breaker, fast-failing requests after repeated failures.
+    pub circuit_breaker: Option<HttpCircuitBreaker>,
+
+    /// Optional maximum number of requests in flight at once, further requests wait.
+    pub max_concurrent_requests: Option<usize>,
+
+    /// Fail requests over `max_concurrent_requests` with `HttpError::TooManyInFlight`
+    /// instead of waiting for a free slot.
+    pub fail_fast_when_busy: bool,
+
+    /// Refuse to send SMS messages while the last-known network status is roaming.
+    pub block_sends_while_roaming: bool,
+
+    /// Optional SIM PIN, supplied by `HttpClient::ensure_sim_unlocked` if the SIM is locked.
+    pub sim_pin: Option<String>,
+
+    /// Request/response hooks, applied to every request in the order they were added.
+    pub middleware: crate::http::middleware::HttpMiddlewareStack,
+
+    /// Optional TTL to reuse modem responses for, eg: signal strength and battery level.
+    pub modem_cache_ttl: Option<std::time::Duration>,
+
+    /// Connection reuse, keep-alive and HTTP version options.
+    pub connection: HttpConnectionConfig,
+
+    /// Which compressed response encodings to accept, requires the `compression` feature.
+    pub compression: HttpCompression,
+}
+#[cfg(feature = "http")]
+impl std::fmt::Debug for HttpConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The SIM PIN is masked, so configs can be logged without leaking it.
+        f.debug_struct("HttpConfig")
+            .field("url", &self.url)
+            .field("authorization", &self.authorization)
+            .field("base_timeout", &self.base_timeout)
+            .field("modem_timeout", &self.modem_timeout)
+            .field("rate_limit", &self.rate_limit)
+            .field("circuit_breaker", &self.circuit_breaker)
+            .field("max_concurrent_requests", &self.max_concurrent_requests)
+            .field("fail_fast_when_busy", &self.fail_fast_when_busy)
+            .field("block_sends_while_roaming", &self.block_sends_while_roaming)
+            .field("sim_pin", &self.sim_pin.as_ref().map(|_| "<redacted>"))
+            .field("middleware", &self.middleware)
+            .field("modem_cache_ttl", &self.modem_cache_ttl)
+            .field("connection", &self.connection)
+            .field("compression", &self.compression)
+            .finish()
+    }
 }
 #[cfg(feature = "http")]
 impl HttpConfig {
@@ -40,6 +93,16 @@ impl HttpConfig {
             modem_timeout: Some(std::time::Duration::from_secs(
                 Self::HTTP_DEFAULT_MODEM_TIMEOUT,
             )),
+            rate_limit: None,
+            circuit_breaker: None,
+            max_concurrent_requests: None,
+            fail_fast_when_busy: false,
+            block_sends_while_roaming: false,
+            sim_pin: None,
+            middleware: crate::http::middleware::HttpMiddlewareStack::default(),
+            modem_cache_ttl: None,
+            connection: HttpConnectionConfig::default(),
+            compression: HttpCompression::default(),
         }
     }
 
@@ -63,6 +126,127 @@ impl HttpConfig {
         self.modem_timeout = timeout;
         self
     }
+
+    /// Limit requests to `requests_per_second`, allowing short bursts of up to `burst`
+    /// requests. Requests over the limit wait rather than fail.
+    #[must_use]
+    pub fn with_rate_limit(mut self, requests_per_second: u32, burst: u32) -> Self {
+        self.rate_limit = Some(HttpRateLimit {
+            requests_per_second,
+            burst,
+        });
+        self
+    }
+
+    /// Open a circuit breaker after `failure_threshold` consecutive failures (connection
+    /// errors, timeouts or 5xx responses). While open, requests fail immediately with
+    /// `HttpError::CircuitOpen`, until a probe request is allowed after `open_duration`.
+    #[must_use]
+    pub fn with_circuit_breaker(
+        mut self,
+        failure_threshold: u32,
+        open_duration: std::time::Duration,
+    ) -> Self {
+        self.circuit_breaker = Some(HttpCircuitBreaker {
+            failure_threshold,
+            open_duration,
+        });
+        self
+    }
+
+    /// Limit how many requests can be in flight at once, shared by all requests from the
+    /// client. Further requests wait for a free slot, so bursts (eg: batch sends) can't
+    /// open many simultaneous connections to the gateway. A zero limit is treated as one.
+    #[must_use]
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = Some(max_concurrent_requests);
+        self
+    }
+
+    /// Fail requests with `HttpError::TooManyInFlight` when `max_concurrent_requests` are
+    /// already in flight, instead of queueing them. This is useful for latency sensitive
+    /// callers that would rather shed load than wait behind slow modem requests.
+    #[must_use]
+    pub fn with_fail_fast_when_busy(mut self, enabled: bool) -> Self {
+        self.fail_fast_when_busy = enabled;
+        self
+    }
+
+    /// Refuse to send SMS messages while roaming, failing with `HttpError::Roaming`.
+    /// This uses the last-known network status, so it should be paired with a
+    /// `RoamingWatcher` (or other regular network status requests) to stay current.
+    #[must_use]
+    pub fn with_block_sends_while_roaming(mut self, enabled: bool) -> Self {
+        self.block_sends_while_roaming = enabled;
+        self
+    }
+
+    /// Set the SIM PIN to supply when the SIM is found to be locked. A `Client` supplies it
+    /// in the background when created (within a Tokio runtime), and again whenever the
+    /// WebSocket reports a modem status update, eg: after the modem restarts.
+    #[must_use]
+    pub fn with_sim_pin(mut self, pin: impl Into<String>) -> Self {
+        self.sim_pin = Some(pin.into());
+        self
+    }
+
+    /// Reuse modem responses (network status, signal strength, battery level etc.) for `ttl`,
+    /// as modem requests are slow. `Client` refreshes cached responses in the background
+    /// before they expire, and `HttpClient::force_refresh` refetches every response on demand.
+    #[must_use]
+    pub fn with_modem_cache(mut self, ttl: std::time::Duration) -> Self {
+        self.modem_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Send TCP keep-alive probes on open connections at `interval`, so dropped connections
+    /// on flaky networks are noticed before a request is sent on them.
+    #[must_use]
+    pub fn with_tcp_keepalive(mut self, interval: std::time::Duration) -> Self {
+        self.connection.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Set how long idle connections are kept open for reuse.
+    #[must_use]
+    pub fn with_pool_idle_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.connection.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum number of idle connections kept open for reuse.
+    /// Zero disables reuse, opening a new connection for every request.
+    #[must_use]
+    pub fn with_pool_max_idle(mut self, max_idle: usize) -> Self {
+        self.connection.pool_max_idle = Some(max_idle);
+        self
+    }
+
+    /// Set which HTTP version to use.
+    #[must_use]
+    pub fn with_http_version(mut self, version: HttpVersionPreference) -> Self {
+        self.connection.version = version;
+        self
+    }
+
+    /// Set which compressed response encodings to accept. With the `compression` feature all
+    /// are accepted by default, which helps large message history pages over slow links.
+    /// Use `HttpCompression::none()` to save CPU on constrained devices.
+    #[must_use]
+    pub fn with_compression(mut self, compression: HttpCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Add a request/response hook, eg: to add custom headers or log responses.
+    #[must_use]
+    pub fn with_middleware(
+        mut self,
+        middleware: impl crate::http::middleware::HttpMiddleware + 'static,
+    ) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
 }
 #[cfg(feature = "http")]
 impl Default for HttpConfig {
@@ -74,9 +258,118 @@ impl Default for HttpConfig {
             modem_timeout: Some(std::time::Duration::from_secs(
                 Self::HTTP_DEFAULT_MODEM_TIMEOUT,
             )),
+            rate_limit: None,
+            circuit_breaker: None,
+            max_concurrent_requests: None,
+            fail_fast_when_busy: false,
+            block_sends_while_roaming: false,
+            sim_pin: None,
+            middleware: crate::http::middleware::HttpMiddlewareStack::default(),
+            modem_cache_ttl: None,
+            connection: HttpConnectionConfig::default(),
+            compression: HttpCompression::default(),
+        }
+    }
+}
+
+/// Client-side token-bucket rate limit for HTTP requests.
+#[cfg(feature = "http")]
+#[derive(Clone, Copy, Debug)]
+pub struct HttpRateLimit {
+    /// The sustained number of requests allowed per second.
+    pub requests_per_second: u32,
+
+    /// The number of requests that can be sent at once before limiting applies.
+    pub burst: u32,
+}
+
+/// HTTP connection options, applied to the underlying `reqwest::Client`.
+#[cfg(feature = "http")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HttpConnectionConfig {
+    /// Optional interval between TCP keep-alive probes.
+    pub tcp_keepalive: Option<std::time::Duration>,
+
+    /// Optional time an idle connection is kept open for reuse.
+    pub pool_idle_timeout: Option<std::time::Duration>,
+
+    /// Optional maximum number of idle connections kept open, zero disables reuse.
+    pub pool_max_idle: Option<usize>,
+
+    /// Which HTTP version to use.
+    pub version: HttpVersionPreference,
+}
+
+/// Compressed response encodings the HTTP client accepts.
+/// These only have an effect with the `compression` feature.
+#[cfg(feature = "http")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct HttpCompression {
+    /// Accept gzip encoded responses.
+    pub gzip: bool,
+
+    /// Accept brotli encoded responses.
+    pub brotli: bool,
+
+    /// Accept deflate encoded responses.
+    pub deflate: bool,
+}
+#[cfg(feature = "http")]
+impl HttpCompression {
+    /// Accept every supported encoding.
+    #[must_use]
+    pub const fn all() -> Self {
+        Self {
+            gzip: true,
+            brotli: true,
+            deflate: true,
+        }
+    }
+
+    /// Only accept uncompressed responses.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            gzip: false,
+            brotli: false,
+            deflate: false,
         }
     }
 }
+#[cfg(feature = "http")]
+impl Default for HttpCompression {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// Which HTTP version the HTTP client uses.
+#[cfg(feature = "http")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HttpVersionPreference {
+    /// Use HTTP/1.1, or HTTP/2 if it's offered over TLS (ALPN) and the `http2` feature is enabled.
+    #[default]
+    Negotiate,
+
+    /// Only use HTTP/1.1.
+    Http1Only,
+
+    /// Use HTTP/2 without negotiation, eg: for a plaintext HTTP/2 server on the LAN.
+    #[cfg(feature = "http2")]
+    Http2PriorKnowledge,
+}
+
+/// HTTP circuit breaker thresholds.
+#[cfg(feature = "http")]
+#[derive(Clone, Copy, Debug)]
+pub struct HttpCircuitBreaker {
+    /// The number of consecutive failures before the circuit opens.
+    pub failure_threshold: u32,
+
+    /// How long the circuit stays open before a probe request is allowed.
+    pub open_duration: std::time::Duration,
+}
 
 /// WebSocket-specific configuration.
 #[cfg(feature = "websocket")]
@@ -94,6 +387,12 @@ pub struct WebSocketConfig {
     /// Interval to use between reconnection attempts.
     pub reconnect_interval: std::time::Duration,
 
+    /// How the reconnect delay grows with each sequential attempt.
+    pub reconnect_backoff: BackoffStrategy,
+
+    /// The maximum delay between reconnection attempts, before any jitter.
+    pub max_reconnect_delay: std::time::Duration,
+
     /// The interval between sending websocket pings.
     pub ping_interval: std::time::Duration,
 
@@ -107,6 +406,23 @@ pub struct WebSocketConfig {
     /// the websocket connection URI, and the server filters out events before
     /// sending them. By default, all events are sent when none are selected.
     pub filtered_events: Option<Vec<String>>,
+
+    /// Optional maximum random delay before the first connection attempt, also added to
+    /// each reconnect delay. This spreads out connections when many clients restart at once.
+    pub connect_jitter: Option<std::time::Duration>,
+
+    /// Optional hint of how quickly the server accepts connections across every client
+    /// instance. Connection attempts are spread over a window long enough for the whole
+    /// fleet to connect within the rate, if that is longer than the connect jitter.
+    pub connect_rate_hint: Option<ConnectRateHint>,
+
+    /// Optional bounded queue between the socket reader and the message callback.
+    /// Without a queue, callbacks are called inline by the socket reader.
+    pub event_queue: Option<EventQueueConfig>,
+
+    /// Optional low-level WebSocket protocol settings (buffer sizes, frame and message
+    /// limits), otherwise the tungstenite defaults are used.
+    pub protocol_config: Option<tungstenite::protocol::WebSocketConfig>,
 }
 #[cfg(feature = "websocket")]
 impl WebSocketConfig {
@@ -114,6 +430,9 @@ impl WebSocketConfig {
     /// Sequential attempts use a backoff up to 60 seconds.
     pub const WS_DEFAULT_RECONNECT_INTERVAL: u64 = 5;
 
+    /// The default maximum delay between connection attempts.
+    pub const WS_DEFAULT_MAX_RECONNECT_DELAY: u64 = 60;
+
     /// The interval between sending ping messages.
     pub const WS_DEFAULT_PING_INTERVAL: u64 = 10;
 
@@ -128,10 +447,18 @@ impl WebSocketConfig {
             authorization: None,
             auto_reconnect: true,
             reconnect_interval: std::time::Duration::from_secs(Self::WS_DEFAULT_RECONNECT_INTERVAL),
+            reconnect_backoff: BackoffStrategy::Linear,
+            max_reconnect_delay: std::time::Duration::from_secs(
+                Self::WS_DEFAULT_MAX_RECONNECT_DELAY,
+            ),
             ping_interval: std::time::Duration::from_secs(Self::WS_DEFAULT_PING_INTERVAL),
             ping_timeout: std::time::Duration::from_secs(Self::WS_DEFAULT_PING_TIMEOUT),
             max_reconnect_attempts: None,
             filtered_events: None,
+            connect_jitter: None,
+            connect_rate_hint: None,
+            event_queue: None,
+            protocol_config: None,
         }
     }
 
@@ -156,6 +483,31 @@ impl WebSocketConfig {
         self
     }
 
+    /// Set how the reconnect delay grows with each attempt, and the maximum delay.
+    /// Any connect jitter is added on top of the capped delay.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use sms_client::config::{BackoffStrategy, WebSocketConfig};
+    ///
+    /// // 1s, 2s, 4s, 8s ... up to 2 minutes, plus up to 5s of jitter.
+    /// let config = WebSocketConfig::new("ws://192.168.1.2:3000/ws")
+    ///     .with_reconnect_interval(Duration::from_secs(1))
+    ///     .with_reconnect_backoff(BackoffStrategy::Exponential, Duration::from_secs(120))
+    ///     .with_connect_jitter(Duration::from_secs(5));
+    /// ```
+    #[must_use]
+    pub fn with_reconnect_backoff(
+        mut self,
+        strategy: BackoffStrategy,
+        max_delay: std::time::Duration,
+    ) -> Self {
+        self.reconnect_backoff = strategy;
+        self.max_reconnect_delay = max_delay;
+        self
+    }
+
     /// Set the ping interval.
     #[must_use]
     pub fn with_ping_interval(mut self, interval: std::time::Duration) -> Self {
@@ -185,6 +537,73 @@ impl WebSocketConfig {
         self.filtered_events = events.map(|events| events.into_iter().map(Into::into).collect());
         self
     }
+
+    /// Set the maximum random delay before connecting and added to each reconnect delay,
+    /// so a fleet of clients restarting together doesn't connect at the same instant.
+    #[must_use]
+    pub fn with_connect_jitter(mut self, max_jitter: std::time::Duration) -> Self {
+        self.connect_jitter = Some(max_jitter);
+        self
+    }
+
+    /// Set the connection rate the server accepts across a fleet of `instances` clients
+    /// sharing it, so restarting together doesn't exceed it. Every instance should be given
+    /// the same hint.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use sms_client::config::{ConnectRateHint, WebSocketConfig};
+    ///
+    /// // 40 instances, with the server accepting a connection every 250ms.
+    /// let config = WebSocketConfig::new("ws://192.168.1.2:3000/ws")
+    ///     .with_connect_rate_hint(ConnectRateHint::new(Duration::from_millis(250), 40));
+    /// ```
+    #[must_use]
+    pub fn with_connect_rate_hint(mut self, hint: ConnectRateHint) -> Self {
+        self.connect_rate_hint = Some(hint);
+        self
+    }
+
+    /// Get the maximum random delay for a connection attempt, from the connect jitter and
+    /// the connect rate hint (whichever is longer).
+    pub(crate) fn connect_spread(&self) -> Option<std::time::Duration> {
+        let hint = self.connect_rate_hint.map(|hint| hint.spread());
+        match (self.connect_jitter, hint) {
+            (Some(jitter), Some(hint)) => Some(jitter.max(hint)),
+            (jitter, hint) => jitter.or(hint),
+        }
+    }
+
+    /// Queue received events for the message callback in a separate task, so a slow
+    /// callback can't stall the socket reader (causing ping timeouts and reconnects).
+    /// The overflow policy decides what happens once `capacity` events are waiting.
+    #[must_use]
+    pub fn with_event_queue(mut self, capacity: usize, overflow: EventQueueOverflow) -> Self {
+        self.event_queue = Some(EventQueueConfig { capacity, overflow });
+        self
+    }
+
+    /// Set low-level WebSocket protocol settings, eg: smaller write buffers on low-memory hosts.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::config::WebSocketConfig;
+    ///
+    /// let protocol = tungstenite::protocol::WebSocketConfig::default()
+    ///     .write_buffer_size(16 * 1024)
+    ///     .max_message_size(Some(1024 * 1024));
+    ///
+    /// let config = WebSocketConfig::new("ws://192.168.1.2:3000/ws").with_protocol_config(protocol);
+    /// ```
+    #[must_use]
+    pub fn with_protocol_config(
+        mut self,
+        protocol_config: tungstenite::protocol::WebSocketConfig,
+    ) -> Self {
+        self.protocol_config = Some(protocol_config);
+        self
+    }
 }
 #[cfg(feature = "websocket")]
 impl Default for WebSocketConfig {
@@ -194,28 +613,385 @@ impl Default for WebSocketConfig {
             authorization: None,
             auto_reconnect: true,
             reconnect_interval: std::time::Duration::from_secs(Self::WS_DEFAULT_RECONNECT_INTERVAL),
+            reconnect_backoff: BackoffStrategy::Linear,
+            max_reconnect_delay: std::time::Duration::from_secs(
+                Self::WS_DEFAULT_MAX_RECONNECT_DELAY,
+            ),
             ping_interval: std::time::Duration::from_secs(Self::WS_DEFAULT_PING_INTERVAL),
             ping_timeout: std::time::Duration::from_secs(Self::WS_DEFAULT_PING_TIMEOUT),
             max_reconnect_attempts: None,
             filtered_events: None,
+            connect_jitter: None,
+            connect_rate_hint: None,
+            event_queue: None,
+            protocol_config: None,
         }
     }
 }
 
-/// WebSocket and HTTP TLS configuration.
+/// How quickly the server accepts WebSocket connections, shared by a fleet of client instances.
+#[cfg(feature = "websocket")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConnectRateHint {
+    /// The minimum gap the server needs between new connections.
+    pub min_interval: std::time::Duration,
+
+    /// The number of client instances connecting to the server.
+    pub instances: u32,
+}
+#[cfg(feature = "websocket")]
+impl ConnectRateHint {
+    /// Create a connect rate hint for `instances` clients sharing a server that accepts a
+    /// connection every `min_interval`.
+    #[must_use]
+    pub fn new(min_interval: std::time::Duration, instances: u32) -> Self {
+        Self {
+            min_interval,
+            instances,
+        }
+    }
+
+    /// The window connection attempts are spread over, long enough for every instance
+    /// to connect one at a time.
+    #[must_use]
+    pub fn spread(&self) -> std::time::Duration {
+        self.min_interval.saturating_mul(self.instances.max(1))
+    }
+}
+
+/// How the delay between WebSocket reconnection attempts grows.
+#[cfg(feature = "websocket")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BackoffStrategy {
+    /// Always wait the reconnect interval.
+    Fixed,
+
+    /// Wait the reconnect interval multiplied by the attempt number.
+    #[default]
+    Linear,
+
+    /// Double the delay with each attempt, starting at the reconnect interval.
+    Exponential,
+}
+#[cfg(feature = "websocket")]
+impl BackoffStrategy {
+    /// Get the delay before a reconnection attempt (starting at 1), capped at `max_delay`.
+    #[must_use]
+    pub fn delay(
+        self,
+        interval: std::time::Duration,
+        attempt: u32,
+        max_delay: std::time::Duration,
+    ) -> std::time::Duration {
+        let delay = match self {
+            Self::Fixed => interval,
+            Self::Linear => interval.saturating_mul(attempt),
+            Self::Exponential => {
+                interval.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            }
+        };
+        delay.min(max_delay)
+    }
+}
+
+/// What to do with a received event when the WebSocket event queue is full.
+#[cfg(feature = "websocket")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventQueueOverflow {
+    /// Stop reading from the socket until there is space.
+    Block,
+
+    /// Drop the oldest queued event to make space.
+    DropOldest,
+
+    /// Drop the event that was just received.
+    DropNewest,
+}
+
+/// WebSocket event queue configuration.
+#[cfg(feature = "websocket")]
+#[derive(Clone, Copy, Debug)]
+pub struct EventQueueConfig {
+    /// The maximum number of events waiting for the callback.
+    pub capacity: usize,
+
+    /// What to do when the queue is full.
+    pub overflow: EventQueueOverflow,
+}
+
+/// Webhook receiver configuration.
+#[cfg(feature = "webhook")]
 #[derive(Clone, Debug)]
+pub struct WebhookConfig {
+    /// The address to listen on. eg: 0.0.0.0:3100
+    pub bind: std::net::SocketAddr,
+
+    /// The request path webhook callbacks are accepted on, others are rejected with 404.
+    pub path: String,
+
+    /// Optional authorization header value callbacks must send, others are rejected with 401.
+    pub authorization: Option<String>,
+
+    /// The largest accepted request body, larger callbacks are rejected with 413.
+    pub max_body_size: usize,
+
+    /// How long a connection may take to send its full request (headers and body)
+    /// before it is closed. This stops slow or idle clients holding connections open.
+    pub read_timeout: std::time::Duration,
+
+    /// The most connections handled at once, further connections wait to be accepted.
+    pub max_connections: usize,
+}
+#[cfg(feature = "webhook")]
+impl WebhookConfig {
+    /// The default path webhook callbacks are accepted on.
+    pub const WEBHOOK_DEFAULT_PATH: &'static str = "/webhook";
+
+    /// The default largest accepted request body, in bytes.
+    pub const WEBHOOK_DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
+
+    /// The default request read timeout, in seconds.
+    pub const WEBHOOK_DEFAULT_READ_TIMEOUT: u64 = 10;
+
+    /// The default most connections handled at once.
+    pub const WEBHOOK_DEFAULT_MAX_CONNECTIONS: usize = 64;
+
+    /// Create a new webhook configuration listening on `bind`, with default settings.
+    #[must_use]
+    pub fn new(bind: std::net::SocketAddr) -> Self {
+        Self {
+            bind,
+            path: Self::WEBHOOK_DEFAULT_PATH.to_string(),
+            authorization: None,
+            max_body_size: Self::WEBHOOK_DEFAULT_MAX_BODY_SIZE,
+            read_timeout: std::time::Duration::from_secs(Self::WEBHOOK_DEFAULT_READ_TIMEOUT),
+            max_connections: Self::WEBHOOK_DEFAULT_MAX_CONNECTIONS,
+        }
+    }
+
+    /// Set the request path webhook callbacks are accepted on.
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Set the authorization header value callbacks must send.
+    #[must_use]
+    pub fn with_auth(mut self, token: impl Into<String>) -> Self {
+        self.authorization = Some(token.into());
+        self
+    }
+
+    /// Set the largest accepted request body, in bytes.
+    #[must_use]
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Set how long a connection may take to send its full request.
+    #[must_use]
+    pub fn with_read_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    /// Set the most connections handled at once. This is clamped to at least 1.
+    #[must_use]
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = max_connections.max(1);
+        self
+    }
+}
+
+/// WebSocket and HTTP TLS configuration.
+#[derive(Clone, Debug, Default)]
 pub struct TLSConfig {
-    /// TLS certificate filepath.
-    pub certificate: std::path::PathBuf,
+    /// TLS certificate, trusted as a root certificate.
+    pub certificate: Option<CertificateSource>,
+
+    /// SHA-256 fingerprint of the server certificate. When set, only a server certificate
+    /// with this fingerprint is accepted. Without a certificate this replaces CA validation,
+    /// otherwise the server certificate must pass both. This requires a rustls TLS feature.
+    pub pinned_sha256: Option<[u8; 32]>,
+
+    /// Client certificate and key presented to the server, for mutual TLS.
+    pub identity: Option<ClientIdentity>,
+
+    /// Also trust the default webpki roots alongside the certificate for WebSocket rustls
+    /// connections. HTTP and native-tls connections always include their default roots.
+    pub include_default_roots: bool,
+
+    /// Accept any server certificate and hostname, disabling verification entirely.
+    /// This is only for lab and development environments.
+    pub danger_accept_invalid_certs: bool,
 }
 impl TLSConfig {
     /// Set a certificate filepath to use for TLS connections.
     pub fn new(certificate: impl Into<std::path::PathBuf>) -> crate::error::ClientResult<Self> {
         Ok(Self {
-            certificate: Self::verify_path(&certificate.into())?,
+            certificate: Some(CertificateSource::File(Self::verify_path(
+                &certificate.into(),
+            )?)),
+            pinned_sha256: None,
+            identity: None,
+            include_default_roots: false,
+            danger_accept_invalid_certs: false,
+        })
+    }
+
+    /// Use an in-memory PEM certificate, eg: from an embedded resource, secrets manager
+    /// or environment variable.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::config::TLSConfig;
+    ///
+    /// let pem = std::env::var("SMS_CA_CERTIFICATE").expect("Missing certificate");
+    /// let tls = TLSConfig::from_pem_bytes(pem)?;
+    /// ```
+    pub fn from_pem_bytes(pem: impl Into<Vec<u8>>) -> crate::error::ClientResult<Self> {
+        let pem = pem.into();
+        if !pem
+            .windows(b"-----BEGIN".len())
+            .any(|window| window == b"-----BEGIN")
+        {
+            return Err(crate::error::ClientError::ConfigError(
+                "Certificate bytes are not PEM encoded",
+            ));
+        }
+
+        Ok(Self {
+            certificate: Some(CertificateSource::Pem(pem)),
+            ..Self::default()
+        })
+    }
+
+    /// Use an in-memory DER certificate.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::config::TLSConfig;
+    ///
+    /// use sms_client::error::ClientResult;
+    ///
+    /// fn tls_from_secret(der: Vec<u8>) -> ClientResult<TLSConfig> {
+    ///     TLSConfig::from_der_bytes(der)
+    /// }
+    /// ```
+    pub fn from_der_bytes(der: impl Into<Vec<u8>>) -> crate::error::ClientResult<Self> {
+        let der = der.into();
+        if der.is_empty() {
+            return Err(crate::error::ClientError::ConfigError(
+                "Certificate bytes are empty",
+            ));
+        }
+
+        Ok(Self {
+            certificate: Some(CertificateSource::Der(der)),
+            ..Self::default()
         })
     }
 
+    /// Accept only a server certificate with a SHA-256 fingerprint, given as hex with
+    /// or without colon separators. This suits self-hosted gateways with self-signed
+    /// certificates, where there is no CA to validate against.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::config::TLSConfig;
+    ///
+    /// let tls = TLSConfig::from_pinned_sha256(
+    ///     "9F:86:D0:81:88:4C:7D:65:9A:2F:EA:A0:C5:5A:D0:15:A3:BF:4F:1B:2B:0B:82:2C:D1:5D:6C:15:B0:F0:0A:08"
+    /// )?;
+    /// ```
+    pub fn from_pinned_sha256(fingerprint: &str) -> crate::error::ClientResult<Self> {
+        Ok(Self {
+            certificate: None,
+            pinned_sha256: Some(parse_sha256_fingerprint(fingerprint)?),
+            identity: None,
+            include_default_roots: false,
+            danger_accept_invalid_certs: false,
+        })
+    }
+
+    /// Also require the server certificate to have a SHA-256 fingerprint, given as hex with
+    /// or without colon separators. The certificate must still be signed by the configured
+    /// root certificate, so a compromised CA can't issue a replacement.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::config::TLSConfig;
+    ///
+    /// let tls = TLSConfig::new("./certificate.crt")?.with_pinned_sha256(
+    ///     "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08"
+    /// )?;
+    /// ```
+    pub fn with_pinned_sha256(mut self, fingerprint: &str) -> crate::error::ClientResult<Self> {
+        self.pinned_sha256 = Some(parse_sha256_fingerprint(fingerprint)?);
+        Ok(self)
+    }
+
+    /// Trust the default webpki roots as well as the configured certificate, so a public CA
+    /// and a private CA can both be used (eg: a public CA for HTTP and a private CA for the
+    /// WebSocket). This only changes WebSocket rustls connections, as HTTP and native-tls
+    /// connections always include their default roots.
+    #[must_use]
+    pub fn with_default_roots(mut self, include: bool) -> Self {
+        self.include_default_roots = include;
+        self
+    }
+
+    /// **Dangerous:** accept any server certificate, including expired, self-signed and
+    /// mismatched hostname certificates. Anyone on the network path can then intercept
+    /// traffic, including the authorization token. Only use this in lab and development
+    /// environments. A pinned fingerprint still takes precedence over this.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::config::TLSConfig;
+    ///
+    /// let tls = TLSConfig::default().danger_accept_invalid_certs(true);
+    /// ```
+    #[must_use]
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.danger_accept_invalid_certs = accept;
+        self
+    }
+
+    /// Present a client certificate to the server, for deployments that require mutual TLS.
+    /// The certificate file is a PEM chain starting with the client certificate, and the key
+    /// file is a PEM private key. PKCS#8 keys work with every TLS backend, rustls also
+    /// accepts PKCS#1 and SEC1 keys.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::config::TLSConfig;
+    ///
+    /// // Trust the server's CA and authenticate with a client certificate.
+    /// let tls = TLSConfig::new("./ca.crt")?.with_client_identity("./client.pem", "./client.key")?;
+    ///
+    /// // Authenticate with a client certificate, trusting the default roots.
+    /// let tls = TLSConfig::default().with_client_identity("./client.pem", "./client.key")?;
+    /// ```
+    pub fn with_client_identity(
+        mut self,
+        certificate: impl Into<std::path::PathBuf>,
+        key: impl Into<std::path::PathBuf>,
+    ) -> crate::error::ClientResult<Self> {
+        let certificate = certificate.into();
+        let key = key.into();
+        if !certificate.is_file() || !key.is_file() {
+            return Err(crate::error::ClientError::ConfigError(
+                "Client identity filepath is not a file",
+            ));
+        }
+
+        self.identity = Some(ClientIdentity { certificate, key });
+        Ok(self)
+    }
+
     /// Verify certificate filepath. Returning Path it's a valid filepath, and it has an appropriate extension.
     fn verify_path(path: &std::path::Path) -> crate::error::ClientResult<std::path::PathBuf> {
         if !path.exists() {
@@ -242,6 +1018,43 @@ impl TLSConfig {
     }
 }
 
+/// Where a TLS root certificate is loaded from.
+#[derive(Clone, Debug)]
+pub enum CertificateSource {
+    /// A PEM or DER certificate file, with a `pem`, `crt` or `der` extension.
+    File(std::path::PathBuf),
+
+    /// In-memory PEM certificate data.
+    Pem(Vec<u8>),
+
+    /// In-memory DER certificate data.
+    Der(Vec<u8>),
+}
+impl CertificateSource {
+    /// Read the certificate data, alongside its format (`pem`, `der`, or `crt` for either)
+    /// if it's known from the source or file extension.
+    pub(crate) fn read(&self) -> std::io::Result<(std::borrow::Cow<'_, [u8]>, Option<&str>)> {
+        match self {
+            Self::File(path) => Ok((
+                std::borrow::Cow::Owned(std::fs::read(path)?),
+                path.extension().and_then(|s| s.to_str()),
+            )),
+            Self::Pem(data) => Ok((std::borrow::Cow::Borrowed(data), Some("pem"))),
+            Self::Der(data) => Ok((std::borrow::Cow::Borrowed(data), Some("der"))),
+        }
+    }
+}
+
+/// A client certificate and private key, presented to the server for mutual TLS.
+#[derive(Clone, Debug)]
+pub struct ClientIdentity {
+    /// PEM certificate chain filepath, starting with the client certificate.
+    pub certificate: std::path::PathBuf,
+
+    /// PEM private key filepath.
+    pub key: std::path::PathBuf,
+}
+
 /// Complete client configuration.
 #[derive(Clone, Debug)]
 pub struct ClientConfig {
@@ -255,6 +1068,9 @@ pub struct ClientConfig {
     /// Optional WebSocket configuration.
     #[cfg(feature = "websocket")]
     pub websocket: Option<WebSocketConfig>,
+
+    /// Optional traffic capture, recording raw HTTP and WebSocket traffic to a file.
+    pub debug_dump: Option<crate::dump::DebugDumpConfig>,
 }
 impl ClientConfig {
     /// Create a new configuration with only HTTP support.
@@ -270,6 +1086,7 @@ impl ClientConfig {
     pub fn http_only(url: impl Into<String>) -> Self {
         Self {
             tls: None,
+            debug_dump: None,
             http: Some(HttpConfig::new(url)),
 
             #[cfg(feature = "websocket")]
@@ -290,6 +1107,7 @@ impl ClientConfig {
     pub fn websocket_only(ws_url: impl Into<String>) -> Self {
         Self {
             tls: None,
+            debug_dump: None,
 
             #[cfg(feature = "http")]
             http: None,
@@ -315,6 +1133,7 @@ impl ClientConfig {
     pub fn both(http_url: impl Into<String>, ws_url: impl Into<String>) -> Self {
         Self {
             tls: None,
+            debug_dump: None,
             http: Some(HttpConfig::new(http_url)),
             websocket: Some(WebSocketConfig::new(ws_url)),
         }
@@ -344,11 +1163,95 @@ impl ClientConfig {
     pub fn from_parts(http: Option<HttpConfig>, websocket: Option<WebSocketConfig>) -> Self {
         Self {
             tls: None,
+            debug_dump: None,
             http,
             websocket,
         }
     }
 
+    /// Create a configuration from a pairing code, eg: scanned from a QR code shown by the
+    /// server. The code is a JSON object, optionally base64 encoded, with the fields:
+    /// - `http`: the HTTP base URL.
+    /// - `ws`: the WebSocket URL.
+    /// - `token`: the authorization token.
+    /// - `fingerprint`: the server certificate's SHA-256 fingerprint, which is pinned.
+    ///
+    /// URLs for components that weren't compiled are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::config::ClientConfig;
+    ///
+    /// let config = ClientConfig::from_pairing_code(
+    ///     r#"{"http":"http://192.168.1.2:3000","ws":"ws://192.168.1.2:3000/ws","token":"abc"}"#
+    /// )?;
+    /// ```
+    pub fn from_pairing_code(code: &str) -> crate::error::ClientResult<Self> {
+        use base64::Engine;
+
+        let code = code.trim();
+        let json = if code.starts_with('{') {
+            code.as_bytes().to_vec()
+        } else {
+            // Accept standard and URL-safe alphabets, with or without padding.
+            let normalized: String = code
+                .chars()
+                .filter(|c| *c != '=')
+                .map(|c| match c {
+                    '+' => '-',
+                    '/' => '_',
+                    c => c,
+                })
+                .collect();
+
+            base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .decode(normalized)
+                .map_err(|_| crate::error::ClientError::ConfigError("Invalid pairing code"))?
+        };
+
+        let payload: PairingPayload = serde_json::from_slice(&json)
+            .map_err(|_| crate::error::ClientError::ConfigError("Invalid pairing code"))?;
+
+        let config = Self {
+            tls: payload
+                .fingerprint
+                .as_deref()
+                .map(TLSConfig::from_pinned_sha256)
+                .transpose()?,
+            debug_dump: None,
+
+            #[cfg(feature = "http")]
+            http: payload.http.map(HttpConfig::new),
+
+            #[cfg(feature = "websocket")]
+            websocket: payload.ws.map(WebSocketConfig::new),
+        };
+        if !config.has_component() {
+            return Err(crate::error::ClientError::ConfigError(
+                "Pairing code has no URL for a compiled component",
+            ));
+        }
+
+        Ok(match payload.token {
+            Some(token) => config.with_auth(token),
+            None => config,
+        })
+    }
+
+    /// Check if at least one of the HTTP or WebSocket components is configured.
+    fn has_component(&self) -> bool {
+        #[cfg(feature = "http")]
+        if self.http.is_some() {
+            return true;
+        }
+
+        #[cfg(feature = "websocket")]
+        if self.websocket.is_some() {
+            return true;
+        }
+        false
+    }
+
     /// Add TLS configuration.
     #[must_use]
     pub fn add_tls(mut self, tls: TLSConfig) -> Self {
@@ -397,13 +1300,31 @@ impl ClientConfig {
         certificate: impl Into<std::path::PathBuf>,
     ) -> crate::error::ClientResult<Self> {
         if let Some(tls) = &mut self.tls {
-            tls.certificate = TLSConfig::verify_path(&certificate.into())?;
+            tls.certificate = Some(CertificateSource::File(TLSConfig::verify_path(
+                &certificate.into(),
+            )?));
         } else {
             self.tls = Some(TLSConfig::new(certificate)?);
         }
         Ok(self)
     }
 
+    /// Record every raw WebSocket frame and HTTP request/response to a rotating file.
+    /// Sensitive headers are redacted. Dumps can be read with `dump::pretty_print`.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::config::ClientConfig;
+    ///
+    /// let config = ClientConfig::http_only("http://192.168.1.2:3000")
+    ///     .with_debug_dump("./sms-dump.jsonl");
+    /// ```
+    #[must_use]
+    pub fn with_debug_dump(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.debug_dump = Some(crate::dump::DebugDumpConfig::new(path));
+        self
+    }
+
     /// Configure the HTTP component if present.
     ///
     /// # Example
@@ -476,6 +1397,7 @@ impl Default for ClientConfig {
     fn default() -> Self {
         Self {
             tls: None,
+            debug_dump: None,
 
             #[cfg(feature = "http")]
             http: Some(HttpConfig::default()),
@@ -509,3 +1431,38 @@ impl From<WebSocketConfig> for ClientConfig {
         }
     }
 }
+
+/// The fields of a `ClientConfig` pairing code.
+#[derive(serde::Deserialize)]
+struct PairingPayload {
+    #[cfg_attr(not(feature = "http"), allow(dead_code))]
+    http: Option<String>,
+    #[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+    ws: Option<String>,
+    token: Option<String>,
+    fingerprint: Option<String>,
+}
+
+/// Parse a hex SHA-256 fingerprint, with or without colon separators.
+fn parse_sha256_fingerprint(fingerprint: &str) -> crate::error::ClientResult<[u8; 32]> {
+    let hex: Vec<u8> = fingerprint
+        .bytes()
+        .filter(|byte| *byte != b':' && !byte.is_ascii_whitespace())
+        .collect();
+
+    let mut bytes = [0u8; 32];
+    if hex.len() != bytes.len() * 2 {
+        return Err(crate::error::ClientError::ConfigError(
+            "Invalid SHA-256 fingerprint",
+        ));
+    }
+    for (byte, pair) in bytes.iter_mut().zip(hex.chunks_exact(2)) {
+        *byte = std::str::from_utf8(pair)
+            .ok()
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+            .ok_or(crate::error::ClientError::ConfigError(
+                "Invalid SHA-256 fingerprint",
+            ))?;
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,235 @@
+//! Cumulative event counters, for exposing basic stats without a metrics stack.
+
+/// A point-in-time copy of the cumulative counters.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CounterSnapshot {
+    /// Messages successfully sent over HTTP.
+    pub messages_sent: u64,
+
+    /// Incoming messages received over the WebSocket.
+    pub messages_received: u64,
+
+    /// Messages found to have permanently failed delivery.
+    pub delivery_permanent_failures: u64,
+
+    /// Messages found to have failed delivery after the SMSC stopped retrying.
+    pub delivery_temporary_failures: u64,
+
+    /// WebSocket reconnection attempts.
+    pub websocket_reconnects: u64,
+
+    /// HTTP responses with a 4xx status.
+    pub http_client_errors: u64,
+
+    /// HTTP responses with a 5xx status.
+    pub http_server_errors: u64,
+
+    /// HTTP requests that failed without a response, eg: connection errors and timeouts.
+    pub http_transport_errors: u64,
+
+    /// HTTP requests that waited for a free slot, as `max_concurrent_requests` were in flight.
+    #[serde(default)]
+    pub http_queue_waits: u64,
+
+    /// Total time HTTP requests spent waiting for a free slot, in milliseconds.
+    #[serde(default)]
+    pub http_queue_wait_ms_total: u64,
+
+    /// Time the most recent queued HTTP request spent waiting for a free slot, in milliseconds.
+    #[serde(default)]
+    pub http_queue_wait_ms_last: u64,
+}
+
+/// The most recent errors kept of each kind, older errors are discarded.
+pub const RECENT_ERRORS_CAPACITY: usize = 16;
+
+/// A recorded error, for diagnosing recent failures.
+#[derive(serde::Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct RecentError {
+    /// When the error happened, in milliseconds since the UNIX epoch.
+    pub at_ms: u128,
+
+    /// The error description.
+    pub message: String,
+}
+
+/// The most recent errors, oldest first. Each kind keeps up to `RECENT_ERRORS_CAPACITY`.
+#[derive(serde::Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RecentErrors {
+    /// WebSocket connection failures and HTTP requests that failed without a response.
+    pub connection: Vec<RecentError>,
+
+    /// Received events that couldn't be decoded.
+    pub parse: Vec<RecentError>,
+}
+
+#[derive(Debug, Default)]
+struct RecentErrorBuffers {
+    connection: std::collections::VecDeque<RecentError>,
+    parse: std::collections::VecDeque<RecentError>,
+}
+
+#[derive(Debug, Default)]
+struct CounterValues {
+    messages_sent: std::sync::atomic::AtomicU64,
+    messages_received: std::sync::atomic::AtomicU64,
+    delivery_permanent_failures: std::sync::atomic::AtomicU64,
+    delivery_temporary_failures: std::sync::atomic::AtomicU64,
+    websocket_reconnects: std::sync::atomic::AtomicU64,
+    http_client_errors: std::sync::atomic::AtomicU64,
+    http_server_errors: std::sync::atomic::AtomicU64,
+    http_transport_errors: std::sync::atomic::AtomicU64,
+    http_queue_waits: std::sync::atomic::AtomicU64,
+    http_queue_wait_ms_total: std::sync::atomic::AtomicU64,
+    http_queue_wait_ms_last: std::sync::atomic::AtomicU64,
+    recent_errors: std::sync::Mutex<RecentErrorBuffers>,
+}
+
+/// A shared handle to the cumulative counters, cheap to clone.
+#[derive(Clone, Debug, Default)]
+pub struct Counters(std::sync::Arc<CounterValues>);
+impl Counters {
+    /// Get a copy of the current counts.
+    #[must_use]
+    pub fn snapshot(&self) -> CounterSnapshot {
+        let load = |counter: &std::sync::atomic::AtomicU64| {
+            counter.load(std::sync::atomic::Ordering::Relaxed)
+        };
+
+        CounterSnapshot {
+            messages_sent: load(&self.0.messages_sent),
+            messages_received: load(&self.0.messages_received),
+            delivery_permanent_failures: load(&self.0.delivery_permanent_failures),
+            delivery_temporary_failures: load(&self.0.delivery_temporary_failures),
+            websocket_reconnects: load(&self.0.websocket_reconnects),
+            http_client_errors: load(&self.0.http_client_errors),
+            http_server_errors: load(&self.0.http_server_errors),
+            http_transport_errors: load(&self.0.http_transport_errors),
+            http_queue_waits: load(&self.0.http_queue_waits),
+            http_queue_wait_ms_total: load(&self.0.http_queue_wait_ms_total),
+            http_queue_wait_ms_last: load(&self.0.http_queue_wait_ms_last),
+        }
+    }
+
+    /// Get a copy of the most recent connection and parse errors.
+    #[must_use]
+    pub fn recent_errors(&self) -> RecentErrors {
+        let buffers = self.recent_error_buffers();
+        RecentErrors {
+            connection: buffers.connection.iter().cloned().collect(),
+            parse: buffers.parse.iter().cloned().collect(),
+        }
+    }
+
+    #[cfg(feature = "http")]
+    pub(crate) fn record_message_sent(&self) {
+        increment(&self.0.messages_sent);
+    }
+
+    /// Count a received event, for incoming messages and delivery report failures.
+    #[cfg(feature = "websocket")]
+    pub(crate) fn record_event(&self, event: &sms_types::events::Event) {
+        match event {
+            sms_types::events::Event::IncomingMessage(_) => increment(&self.0.messages_received),
+            sms_types::events::Event::DeliveryReport { report, .. } => {
+                self.record_delivery(crate::types::delivery::DeliveryStatusGroup::from_tp_status(
+                    report.status,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    /// Count a final delivery outcome, which is ignored unless it's a failure.
+    #[cfg(any(feature = "http", feature = "websocket"))]
+    pub(crate) fn record_delivery(&self, group: crate::types::delivery::DeliveryStatusGroup) {
+        use crate::types::delivery::DeliveryStatusGroup;
+
+        match group {
+            DeliveryStatusGroup::PermanentFailure => {
+                increment(&self.0.delivery_permanent_failures);
+            }
+            DeliveryStatusGroup::TemporaryFailureStopped => {
+                increment(&self.0.delivery_temporary_failures);
+            }
+            DeliveryStatusGroup::Completed | DeliveryStatusGroup::TemporaryFailureRetrying => {}
+        }
+    }
+
+    #[cfg(feature = "websocket")]
+    pub(crate) fn record_reconnect(&self) {
+        increment(&self.0.websocket_reconnects);
+    }
+
+    /// Count time an HTTP request spent waiting for a free request slot.
+    #[cfg(feature = "http")]
+    pub(crate) fn record_queue_wait(&self, wait: std::time::Duration) {
+        let wait_ms = u64::try_from(wait.as_millis()).unwrap_or(u64::MAX);
+        increment(&self.0.http_queue_waits);
+        self.0
+            .http_queue_wait_ms_total
+            .fetch_add(wait_ms, std::sync::atomic::Ordering::Relaxed);
+        self.0
+            .http_queue_wait_ms_last
+            .store(wait_ms, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Keep a WebSocket connection failure, or an HTTP request that failed without a response.
+    #[cfg(any(feature = "http", feature = "websocket"))]
+    pub(crate) fn record_connection_error(&self, error: &impl std::fmt::Display) {
+        push_recent(&mut self.recent_error_buffers().connection, error);
+    }
+
+    /// Keep an event decode failure.
+    #[cfg(feature = "websocket")]
+    pub(crate) fn record_parse_error(&self, error: &impl std::fmt::Display) {
+        push_recent(&mut self.recent_error_buffers().parse, error);
+    }
+
+    /// Count an HTTP request result, by its error class.
+    #[cfg(feature = "http")]
+    pub(crate) fn record_http_result<E>(&self, result: &Result<reqwest::Response, E>)
+    where
+        E: std::fmt::Display,
+    {
+        match result {
+            Ok(response) if response.status().is_client_error() => {
+                increment(&self.0.http_client_errors);
+            }
+            Ok(response) if response.status().is_server_error() => {
+                increment(&self.0.http_server_errors);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                increment(&self.0.http_transport_errors);
+                self.record_connection_error(e);
+            }
+        }
+    }
+
+    fn recent_error_buffers(&self) -> std::sync::MutexGuard<'_, RecentErrorBuffers> {
+        match self.0.recent_errors.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(any(feature = "http", feature = "websocket"))]
+fn push_recent(
+    buffer: &mut std::collections::VecDeque<RecentError>,
+    error: &impl std::fmt::Display,
+) {
+    if buffer.len() >= RECENT_ERRORS_CAPACITY {
+        buffer.pop_front();
+    }
+    buffer.push_back(RecentError {
+        at_ms: crate::dump::now_ms(),
+        message: error.to_string(),
+    });
+}
+
+#[cfg_attr(not(any(feature = "http", feature = "websocket")), allow(dead_code))]
+fn increment(counter: &std::sync::atomic::AtomicU64) {
+    counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+}
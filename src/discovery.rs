@@ -0,0 +1,123 @@
+//! Find SMS gateways on the local network with mDNS, so no IP address needs configuring.
+//!
+//! Gateways advertise an `_sms-api._tcp` service. These optional TXT properties are read:
+//! - `tls`: `1` or `true` if the gateway only accepts TLS connections.
+//! - `ws_path`: the WebSocket path, defaulting to `/ws`.
+
+/// The mDNS service type advertised by gateways.
+pub const SERVICE_TYPE: &str = "_sms-api._tcp.local.";
+
+/// A gateway found on the local network.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscoveredGateway {
+    /// The full mDNS service instance name.
+    pub name: String,
+
+    /// The advertised hostname, without the trailing dot.
+    pub hostname: String,
+
+    /// The resolved addresses, with IPv4 addresses first.
+    pub addresses: Vec<std::net::IpAddr>,
+
+    /// The port the gateway serves HTTP and WebSocket connections on.
+    pub port: u16,
+
+    /// If the gateway only accepts TLS connections.
+    pub tls: bool,
+
+    /// The WebSocket path, eg: `/ws`.
+    pub websocket_path: String,
+}
+impl DiscoveredGateway {
+    fn from_info(info: &mdns_sd::ServiceInfo) -> Self {
+        let mut addresses: Vec<std::net::IpAddr> = info.get_addresses().iter().copied().collect();
+        addresses.sort_by_key(|address| (address.is_ipv6(), *address));
+
+        Self {
+            name: info.get_fullname().to_string(),
+            hostname: info.get_hostname().trim_end_matches('.').to_string(),
+            addresses,
+            port: info.get_port(),
+            tls: matches!(info.get_property_val_str("tls"), Some("1" | "true")),
+            websocket_path: info
+                .get_property_val_str("ws_path")
+                .unwrap_or("/ws")
+                .to_string(),
+        }
+    }
+
+    /// The host to connect to, preferring a resolved address over the hostname.
+    fn host(&self) -> String {
+        match self.addresses.first() {
+            Some(std::net::IpAddr::V4(address)) => address.to_string(),
+            Some(std::net::IpAddr::V6(address)) => format!("[{address}]"),
+            None => self.hostname.clone(),
+        }
+    }
+
+    /// Get the HTTP base URL, eg: `http://192.168.1.2:3000`.
+    #[must_use]
+    pub fn http_url(&self) -> String {
+        let scheme = if self.tls { "https" } else { "http" };
+        format!("{scheme}://{}:{}", self.host(), self.port)
+    }
+
+    /// Get the WebSocket URL, eg: `ws://192.168.1.2:3000/ws`.
+    #[must_use]
+    pub fn websocket_url(&self) -> String {
+        let scheme = if self.tls { "wss" } else { "ws" };
+        format!(
+            "{scheme}://{}:{}/{}",
+            self.host(),
+            self.port,
+            self.websocket_path.trim_start_matches('/')
+        )
+    }
+
+    /// Create a client configuration for this gateway, using every compiled client.
+    /// Authorization and TLS certificates can then be added as usual.
+    #[cfg(any(feature = "http", feature = "websocket"))]
+    #[must_use]
+    pub fn to_config(&self) -> crate::config::ClientConfig {
+        #[cfg(all(feature = "http", feature = "websocket"))]
+        return crate::config::ClientConfig::both(self.http_url(), self.websocket_url());
+
+        #[cfg(all(feature = "http", not(feature = "websocket")))]
+        return crate::config::ClientConfig::http_only(self.http_url());
+
+        #[cfg(all(feature = "websocket", not(feature = "http")))]
+        return crate::config::ClientConfig::websocket_only(self.websocket_url());
+    }
+}
+
+/// Browse for gateways until the timeout elapses, returning each one found.
+pub(crate) async fn browse(
+    timeout: std::time::Duration,
+) -> crate::error::ClientResult<Vec<DiscoveredGateway>> {
+    let daemon = mdns_sd::ServiceDaemon::new()?;
+    let receiver = daemon.browse(SERVICE_TYPE)?;
+
+    // The receiver is blocking, so collect events away from the async runtime.
+    let gateways = tokio::task::spawn_blocking(move || {
+        let deadline = std::time::Instant::now() + timeout;
+        let mut gateways: Vec<DiscoveredGateway> = Vec::new();
+
+        while let Some(remaining) = deadline.checked_duration_since(std::time::Instant::now()) {
+            let Ok(event) = receiver.recv_timeout(remaining) else {
+                break;
+            };
+            if let mdns_sd::ServiceEvent::ServiceResolved(info) = event {
+                let gateway = DiscoveredGateway::from_info(&info);
+                if !gateways.iter().any(|found| found.name == gateway.name) {
+                    gateways.push(gateway);
+                }
+            }
+        }
+        gateways
+    })
+    .await
+    .unwrap_or_default();
+
+    let _ = daemon.shutdown();
+    Ok(gateways)
+}
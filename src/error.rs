@@ -13,10 +13,24 @@ pub enum ClientError {
     #[error("{0}")]
     WebsocketError(#[from] crate::ws::error::WebsocketError),
 
+    /// System IO error
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+
     /// Missing/invalid configuration value
     #[error("Missing/invalid required configuration: {0}")]
     ConfigError(&'static str),
 
+    /// No final delivery report was received for the message before the timeout
+    #[cfg(feature = "http")]
+    #[error("Timed out waiting for delivery of message {0}")]
+    DeliveryTimeout(i64),
+
+    /// mDNS gateway discovery error
+    #[cfg(feature = "discovery")]
+    #[error("Discovery error: {0}")]
+    DiscoveryError(#[from] mdns_sd::Error),
+
     /// No WebSocket client initialized
     #[cfg(feature = "websocket")]
     #[error("No WebSocket client initialized")]
@@ -0,0 +1,337 @@
+//! C ABI for using the client from other languages, declared in `include/sms_client.h`.
+//! Build a shared or static library with `cargo rustc --release --features ffi --crate-type cdylib`
+//! (or `staticlib`). The header can be regenerated with `cbindgen --config cbindgen.toml`.
+//!
+//! Functions returning `int` return `SMS_OK` (0) on success, or a negative error code.
+//! A description of the last error on the calling thread is available from
+//! `sms_client_last_error`. Received events are queued as JSON strings until polled, up to
+//! `EVENT_QUEUE_DEFAULT_CAPACITY` events after which the oldest are dropped.
+
+#![allow(unsafe_code)]
+
+use std::ffi::{CStr, CString, c_char, c_int};
+
+/// The call succeeded.
+pub const SMS_OK: c_int = 0;
+
+/// A required argument was null, or a string was not valid UTF-8.
+pub const SMS_ERR_INVALID_ARGUMENT: c_int = -1;
+
+/// The client returned an error, see `sms_client_last_error`.
+pub const SMS_ERR_CLIENT: c_int = -2;
+
+/// The default most events queued for `sms_client_poll_event`.
+pub const EVENT_QUEUE_DEFAULT_CAPACITY: usize = 1024;
+
+thread_local! {
+    static LAST_ERROR: std::cell::RefCell<Option<CString>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Store the last error for the calling thread, returning its error code.
+fn set_last_error(code: c_int, message: impl std::fmt::Display) -> c_int {
+    let message = CString::new(message.to_string().replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+    code
+}
+
+/// Read an optional C string argument, where null is None.
+///
+/// # Safety
+/// `value` must be null or a valid NUL-terminated string.
+unsafe fn read_str<'a>(value: *const c_char) -> Result<Option<&'a str>, c_int> {
+    if value.is_null() {
+        return Ok(None);
+    }
+
+    // SAFETY: The caller guarantees a valid NUL-terminated string.
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map(Some)
+        .map_err(|e| set_last_error(SMS_ERR_INVALID_ARGUMENT, e))
+}
+
+/// Read a required C string argument.
+///
+/// # Safety
+/// `value` must be null or a valid NUL-terminated string.
+unsafe fn read_required_str<'a>(value: *const c_char, name: &str) -> Result<&'a str, c_int> {
+    // SAFETY: Forwarded from the caller.
+    unsafe { read_str(value) }?
+        .ok_or_else(|| set_last_error(SMS_ERR_INVALID_ARGUMENT, format!("{name} must not be null")))
+}
+
+/// Received events waiting to be polled, bounded so an application that stops polling
+/// can't grow memory without limit.
+struct PolledEvents {
+    events: std::collections::VecDeque<String>,
+    capacity: usize,
+    overflow: crate::config::EventQueueOverflow,
+}
+impl PolledEvents {
+    /// Add an event, applying the overflow policy if the queue is full. Blocking isn't
+    /// possible here, as it would stall dispatch until polled, so it drops the oldest event.
+    fn push(&mut self, json: String) {
+        use crate::config::EventQueueOverflow;
+
+        while self.events.len() >= self.capacity {
+            match self.overflow {
+                EventQueueOverflow::DropNewest => return,
+                EventQueueOverflow::DropOldest | EventQueueOverflow::Block => {
+                    self.events.pop_front();
+                }
+            }
+        }
+        self.events.push_back(json);
+    }
+}
+impl Default for PolledEvents {
+    fn default() -> Self {
+        Self {
+            events: std::collections::VecDeque::new(),
+            capacity: EVENT_QUEUE_DEFAULT_CAPACITY,
+            overflow: crate::config::EventQueueOverflow::DropOldest,
+        }
+    }
+}
+
+/// An opaque client handle, owning the client and the runtime that drives it.
+pub struct SmsClient {
+    client: crate::Client,
+    runtime: tokio::runtime::Runtime,
+    events: std::sync::Arc<std::sync::Mutex<PolledEvents>>,
+}
+impl SmsClient {
+    fn lock_events(&self) -> std::sync::MutexGuard<'_, PolledEvents> {
+        match self.events.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Create a client. `http_url` is required, `ws_url` and `authorization` may be null.
+/// Returns null on failure. The handle must be freed with `sms_client_free`.
+///
+/// # Safety
+/// Every non-null argument must be a valid NUL-terminated string.
+#[must_use]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sms_client_new(
+    http_url: *const c_char,
+    ws_url: *const c_char,
+    authorization: *const c_char,
+) -> *mut SmsClient {
+    // SAFETY: Forwarded from the caller.
+    let args = unsafe {
+        (
+            read_required_str(http_url, "http_url"),
+            read_str(ws_url),
+            read_str(authorization),
+        )
+    };
+    let (Ok(http_url), Ok(ws_url), Ok(authorization)) = args else {
+        return std::ptr::null_mut();
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            set_last_error(SMS_ERR_CLIENT, e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let mut config = crate::config::ClientConfig::from_parts(
+        Some(crate::config::HttpConfig::new(http_url)),
+        ws_url.map(crate::config::WebSocketConfig::new),
+    );
+    if let Some(authorization) = authorization {
+        config = config.with_auth(authorization);
+    }
+
+    let client = {
+        let _guard = runtime.enter();
+        crate::Client::new(config)
+    };
+    match client {
+        Ok(client) => Box::into_raw(Box::new(SmsClient {
+            client,
+            runtime,
+            events: std::sync::Arc::default(),
+        })),
+        Err(e) => {
+            set_last_error(SMS_ERR_CLIENT, e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Free a client handle, stopping its WebSocket connection. Null is ignored.
+///
+/// # Safety
+/// `client` must be null or a handle from `sms_client_new` that has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sms_client_free(client: *mut SmsClient) {
+    if client.is_null() {
+        return;
+    }
+
+    // SAFETY: The caller guarantees the handle is live and not used again.
+    let client = unsafe { Box::from_raw(client) };
+    let _ = client
+        .runtime
+        .block_on(client.client.stop_background_websocket());
+}
+
+/// Send an SMS message. On success the server message ID is written to `message_id`,
+/// if it is not null.
+///
+/// # Safety
+/// `client` must be a live handle, `to` and `content` valid NUL-terminated strings,
+/// and `message_id` null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sms_client_send_sms(
+    client: *const SmsClient,
+    to: *const c_char,
+    content: *const c_char,
+    message_id: *mut i64,
+) -> c_int {
+    // SAFETY: The caller guarantees a live handle.
+    let Some(client) = (unsafe { client.as_ref() }) else {
+        return set_last_error(SMS_ERR_INVALID_ARGUMENT, "client must not be null");
+    };
+    // SAFETY: Forwarded from the caller.
+    let args = unsafe {
+        (
+            read_required_str(to, "to"),
+            read_required_str(content, "content"),
+        )
+    };
+    let (to, content) = match args {
+        (Ok(to), Ok(content)) => (to, content),
+        (Err(code), _) | (_, Err(code)) => return code,
+    };
+
+    let message = sms_types::sms::SmsOutgoingMessage::simple_message(to, content);
+    let result: crate::error::ClientResult<_> = client
+        .runtime
+        .block_on(async { Ok(client.client.http()?.send_sms(&message).await?) });
+    match result {
+        Ok(response) => {
+            if !message_id.is_null() {
+                // SAFETY: The caller guarantees the pointer is valid for writes.
+                unsafe { message_id.write(response.message_id) };
+            }
+            SMS_OK
+        }
+        Err(e) => set_last_error(SMS_ERR_CLIENT, e),
+    }
+}
+
+/// Start the WebSocket connection in the background, queueing every received event
+/// as a JSON string to be read with `sms_client_poll_event`.
+///
+/// # Safety
+/// `client` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sms_client_start_events(client: *const SmsClient) -> c_int {
+    // SAFETY: The caller guarantees a live handle.
+    let Some(client) = (unsafe { client.as_ref() }) else {
+        return set_last_error(SMS_ERR_INVALID_ARGUMENT, "client must not be null");
+    };
+
+    let events = std::sync::Arc::clone(&client.events);
+    let result = client.runtime.block_on(async {
+        client
+            .client
+            .on_message(move |event| {
+                let Ok(json) = serde_json::to_string(&event) else {
+                    return;
+                };
+                match events.lock() {
+                    Ok(mut guard) => guard.push(json),
+                    Err(poisoned) => poisoned.into_inner().push(json),
+                }
+            })
+            .await?;
+        client.client.start_background_websocket().await
+    });
+    match result {
+        Ok(()) => SMS_OK,
+        Err(e) => set_last_error(SMS_ERR_CLIENT, e),
+    }
+}
+
+/// Set the most queued events (at least 1), and whether a full queue drops the newest
+/// event instead of the oldest. Already queued events over the new capacity are dropped.
+///
+/// # Safety
+/// `client` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sms_client_set_event_queue(
+    client: *const SmsClient,
+    capacity: usize,
+    drop_newest: bool,
+) -> c_int {
+    // SAFETY: The caller guarantees a live handle.
+    let Some(client) = (unsafe { client.as_ref() }) else {
+        return set_last_error(SMS_ERR_INVALID_ARGUMENT, "client must not be null");
+    };
+
+    let mut events = client.lock_events();
+    events.capacity = capacity.max(1);
+    events.overflow = if drop_newest {
+        crate::config::EventQueueOverflow::DropNewest
+    } else {
+        crate::config::EventQueueOverflow::DropOldest
+    };
+
+    let excess = events.events.len().saturating_sub(events.capacity);
+    events.events.drain(..excess);
+    SMS_OK
+}
+
+/// Take the oldest queued event as a JSON string, or null if there are none.
+/// The string must be freed with `sms_client_string_free`.
+///
+/// # Safety
+/// `client` must be a live handle.
+#[must_use]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sms_client_poll_event(client: *const SmsClient) -> *mut c_char {
+    // SAFETY: The caller guarantees a live handle.
+    let Some(client) = (unsafe { client.as_ref() }) else {
+        return std::ptr::null_mut();
+    };
+
+    client
+        .lock_events()
+        .events
+        .pop_front()
+        .and_then(|json| CString::new(json).ok())
+        .map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Free a string returned by this library. Null is ignored.
+///
+/// # Safety
+/// `value` must be null or a string from `sms_client_poll_event` that has not been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sms_client_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        // SAFETY: The caller guarantees the string came from `CString::into_raw`.
+        drop(unsafe { CString::from_raw(value) });
+    }
+}
+
+/// Get a description of the last error on the calling thread, or null if there was none.
+/// The string is owned by the library and valid until the next failing call on this thread.
+#[must_use]
+#[unsafe(no_mangle)]
+pub extern "C" fn sms_client_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
@@ -0,0 +1,64 @@
+//! An optional process-wide client, for applications that want to use one client from many
+//! modules without passing it around.
+//!
+//! # Example
+//! ```
+//! use sms_client::config::ClientConfig;
+//! use sms_client::error::ClientResult;
+//!
+//! fn setup() -> ClientResult<()> {
+//!     sms_client::global::init(ClientConfig::http_only("http://192.168.1.2:3000"))?;
+//!     Ok(())
+//! }
+//!
+//! fn elsewhere() {
+//!     if let Some(client) = sms_client::global::client() {
+//!         // Use the client.
+//!     }
+//! }
+//! ```
+
+static CLIENT: std::sync::OnceLock<crate::Client> = std::sync::OnceLock::new();
+static SHUT_DOWN: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Create the global client. This can only be done once per process.
+pub fn init(
+    config: crate::config::ClientConfig,
+) -> crate::error::ClientResult<&'static crate::Client> {
+    let client = crate::Client::new(config)?;
+    CLIENT.set(client).map_err(|_| {
+        crate::error::ClientError::ConfigError("Global client is already initialized")
+    })?;
+
+    CLIENT.get().ok_or(crate::error::ClientError::ConfigError(
+        "Global client is not initialized",
+    ))
+}
+
+/// Get the global client, or None if it hasn't been initialized or has been shut down.
+pub fn client() -> Option<&'static crate::Client> {
+    if SHUT_DOWN.load(std::sync::atomic::Ordering::Acquire) {
+        return None;
+    }
+    CLIENT.get()
+}
+
+/// Shut down the global client, so `client()` returns None. If there is a WebSocket
+/// connection, it is stopped and given up to `grace` for handlers to finish. Returns true
+/// if everything finished in time.
+#[cfg_attr(not(feature = "websocket"), allow(clippy::unused_async))]
+pub async fn shutdown(grace: std::time::Duration) -> crate::error::ClientResult<bool> {
+    if SHUT_DOWN.swap(true, std::sync::atomic::Ordering::AcqRel) {
+        return Ok(true);
+    }
+
+    #[cfg(feature = "websocket")]
+    if let Some(client) = CLIENT.get()
+        && client.ws_client.is_some()
+    {
+        return client.shutdown_websocket(grace).await;
+    }
+
+    let _ = grace;
+    Ok(true)
+}
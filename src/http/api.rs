@@ -0,0 +1,300 @@
+//! Trait abstraction over the SMS-API HTTP methods.
+
+use crate::http::HttpClient;
+use crate::http::capabilities::ServerCapabilities;
+use crate::http::error::HttpResult;
+use crate::http::meta::WithMeta;
+use crate::http::mms::{HttpOutgoingMmsMessage, MmsSendResult};
+use crate::types::modem::{ModemRestartResponse, SimPinStatus};
+use sms_types::gnss::{FixStatus, PositionReport};
+use sms_types::http::{
+    HttpModemBatteryLevelResponse, HttpModemNetworkOperatorResponse,
+    HttpModemNetworkStatusResponse, HttpModemSignalStrengthResponse, HttpPaginationOptions,
+    HttpSmsDeviceInfoData, HttpSmsSendResponse, LatestNumberFriendlyNamePair,
+};
+use sms_types::sms::{SmsDeliveryReport, SmsMessage, SmsOutgoingMessage};
+
+/// The SMS-API HTTP methods, implemented by `HttpClient`. Application code can be
+/// written against this trait, allowing mocks or decorators to be swapped in.
+///
+/// # Example
+/// ```
+/// use sms_client::http::SmsApi;
+/// use sms_client::http::error::HttpResult;
+/// use sms_client::types::sms::SmsOutgoingMessage;
+///
+/// async fn notify(api: &impl SmsApi, phone_number: &str) -> HttpResult<()> {
+///     let message = SmsOutgoingMessage::simple_message(phone_number, "Hello!");
+///     api.send_sms(&message).await?;
+///     Ok(())
+/// }
+/// ```
+pub trait SmsApi {
+    /// Set/Remove the friendly name for a given phone number.
+    fn set_friendly_name(
+        &self,
+        phone_number: &str,
+        friendly_name: Option<&str>,
+    ) -> impl Future<Output = HttpResult<bool>> + Send;
+
+    /// Get the friendly name associated with a given phone number.
+    fn get_friendly_name(
+        &self,
+        phone_number: &str,
+    ) -> impl Future<Output = HttpResult<Option<String>>> + Send;
+
+    /// Get messages sent to and from a given phone number.
+    fn get_messages(
+        &self,
+        phone_number: &str,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> impl Future<Output = HttpResult<Vec<SmsMessage>>> + Send;
+
+    /// Get the latest phone numbers that have been in contact with the SMS-API.
+    fn get_latest_numbers(
+        &self,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> impl Future<Output = HttpResult<Vec<LatestNumberFriendlyNamePair>>> + Send;
+
+    /// Get received delivery reports for a given `message_id`.
+    fn get_delivery_reports(
+        &self,
+        message_id: i64,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> impl Future<Output = HttpResult<Vec<SmsDeliveryReport>>> + Send;
+
+    /// Send an SMS message to a target `phone_number`.
+    fn send_sms(
+        &self,
+        message: &SmsOutgoingMessage,
+    ) -> impl Future<Output = HttpResult<HttpSmsSendResponse>> + Send;
+
+    /// Send an SMS message, also returning selected response headers.
+    fn send_sms_with_meta(
+        &self,
+        message: &SmsOutgoingMessage,
+    ) -> impl Future<Output = HttpResult<WithMeta<HttpSmsSendResponse>>> + Send;
+
+    /// Send a batch of SMS messages, returning a result for each message in the same order.
+    fn send_sms_batch(
+        &self,
+        messages: &[SmsOutgoingMessage],
+    ) -> impl Future<Output = Vec<HttpResult<HttpSmsSendResponse>>> + Send;
+
+    /// Send an MMS message if the server supports it, otherwise send its fallback SMS.
+    fn send_mms(
+        &self,
+        message: &HttpOutgoingMmsMessage,
+        capabilities: &ServerCapabilities,
+    ) -> impl Future<Output = HttpResult<MmsSendResult>> + Send;
+
+    /// Get the carrier network status.
+    fn get_network_status(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemNetworkStatusResponse>> + Send;
+
+    /// Get the modem signal strength for the connected tower.
+    fn get_signal_strength(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemSignalStrengthResponse>> + Send;
+
+    /// Get the underlying network operator.
+    fn get_network_operator(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemNetworkOperatorResponse>> + Send;
+
+    /// Get the SIM service provider.
+    fn get_service_provider(&self) -> impl Future<Output = HttpResult<String>> + Send;
+
+    /// Get the Modem Hat's battery level.
+    fn get_battery_level(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemBatteryLevelResponse>> + Send;
+
+    /// Get the GNSS module's fix status.
+    fn get_gnss_status(&self) -> impl Future<Output = HttpResult<FixStatus>> + Send;
+
+    /// Get the GNSS module's current location.
+    fn get_gnss_location(&self) -> impl Future<Output = HttpResult<PositionReport>> + Send;
+
+    /// Power on the GNSS receiver.
+    fn enable_gnss(&self) -> impl Future<Output = HttpResult<bool>> + Send;
+
+    /// Power off the GNSS receiver.
+    fn disable_gnss(&self) -> impl Future<Output = HttpResult<bool>> + Send;
+
+    /// Set how often the GNSS receiver sends unsolicited position reports.
+    fn set_gnss_report_interval(
+        &self,
+        interval: std::time::Duration,
+    ) -> impl Future<Output = HttpResult<bool>> + Send;
+
+    /// Get the SIM lock state.
+    fn get_pin_status(&self) -> impl Future<Output = HttpResult<SimPinStatus>> + Send;
+
+    /// Unlock the SIM with its PIN.
+    fn unlock_sim(&self, pin: &str) -> impl Future<Output = HttpResult<bool>> + Send;
+
+    /// Restart the modem.
+    fn restart_modem(&self) -> impl Future<Output = HttpResult<ModemRestartResponse>> + Send;
+
+    /// Get device info summary result.
+    fn get_device_info(&self) -> impl Future<Output = HttpResult<HttpSmsDeviceInfoData>> + Send;
+
+    /// Get the configured sender SMS number.
+    fn get_phone_number(&self) -> impl Future<Output = HttpResult<Option<String>>> + Send;
+
+    /// Get the modem SMS-API version string.
+    fn get_version(&self) -> impl Future<Output = HttpResult<String>> + Send;
+
+    /// Get the server capabilities, parsed from its version string.
+    fn get_capabilities(&self) -> impl Future<Output = HttpResult<ServerCapabilities>> + Send;
+}
+
+impl SmsApi for HttpClient {
+    fn set_friendly_name(
+        &self,
+        phone_number: &str,
+        friendly_name: Option<&str>,
+    ) -> impl Future<Output = HttpResult<bool>> + Send {
+        HttpClient::set_friendly_name(self, phone_number, friendly_name)
+    }
+
+    fn get_friendly_name(
+        &self,
+        phone_number: &str,
+    ) -> impl Future<Output = HttpResult<Option<String>>> + Send {
+        HttpClient::get_friendly_name(self, phone_number)
+    }
+
+    fn get_messages(
+        &self,
+        phone_number: &str,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> impl Future<Output = HttpResult<Vec<SmsMessage>>> + Send {
+        HttpClient::get_messages(self, phone_number, pagination)
+    }
+
+    fn get_latest_numbers(
+        &self,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> impl Future<Output = HttpResult<Vec<LatestNumberFriendlyNamePair>>> + Send {
+        HttpClient::get_latest_numbers(self, pagination)
+    }
+
+    fn get_delivery_reports(
+        &self,
+        message_id: i64,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> impl Future<Output = HttpResult<Vec<SmsDeliveryReport>>> + Send {
+        HttpClient::get_delivery_reports(self, message_id, pagination)
+    }
+
+    fn send_sms(
+        &self,
+        message: &SmsOutgoingMessage,
+    ) -> impl Future<Output = HttpResult<HttpSmsSendResponse>> + Send {
+        HttpClient::send_sms(self, message)
+    }
+
+    fn send_sms_with_meta(
+        &self,
+        message: &SmsOutgoingMessage,
+    ) -> impl Future<Output = HttpResult<WithMeta<HttpSmsSendResponse>>> + Send {
+        HttpClient::send_sms_with_meta(self, message)
+    }
+
+    fn send_sms_batch(
+        &self,
+        messages: &[SmsOutgoingMessage],
+    ) -> impl Future<Output = Vec<HttpResult<HttpSmsSendResponse>>> + Send {
+        HttpClient::send_sms_batch(self, messages)
+    }
+
+    fn send_mms(
+        &self,
+        message: &HttpOutgoingMmsMessage,
+        capabilities: &ServerCapabilities,
+    ) -> impl Future<Output = HttpResult<MmsSendResult>> + Send {
+        HttpClient::send_mms(self, message, capabilities)
+    }
+
+    fn get_network_status(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemNetworkStatusResponse>> + Send {
+        HttpClient::get_network_status(self)
+    }
+
+    fn get_signal_strength(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemSignalStrengthResponse>> + Send {
+        HttpClient::get_signal_strength(self)
+    }
+
+    fn get_network_operator(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemNetworkOperatorResponse>> + Send {
+        HttpClient::get_network_operator(self)
+    }
+
+    fn get_service_provider(&self) -> impl Future<Output = HttpResult<String>> + Send {
+        HttpClient::get_service_provider(self)
+    }
+
+    fn get_battery_level(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemBatteryLevelResponse>> + Send {
+        HttpClient::get_battery_level(self)
+    }
+
+    fn get_gnss_status(&self) -> impl Future<Output = HttpResult<FixStatus>> + Send {
+        HttpClient::get_gnss_status(self)
+    }
+
+    fn get_gnss_location(&self) -> impl Future<Output = HttpResult<PositionReport>> + Send {
+        HttpClient::get_gnss_location(self)
+    }
+
+    fn enable_gnss(&self) -> impl Future<Output = HttpResult<bool>> + Send {
+        HttpClient::enable_gnss(self)
+    }
+
+    fn disable_gnss(&self) -> impl Future<Output = HttpResult<bool>> + Send {
+        HttpClient::disable_gnss(self)
+    }
+
+    fn set_gnss_report_interval(
+        &self,
+        interval: std::time::Duration,
+    ) -> impl Future<Output = HttpResult<bool>> + Send {
+        HttpClient::set_gnss_report_interval(self, interval)
+    }
+
+    fn get_pin_status(&self) -> impl Future<Output = HttpResult<SimPinStatus>> + Send {
+        HttpClient::get_pin_status(self)
+    }
+
+    fn unlock_sim(&self, pin: &str) -> impl Future<Output = HttpResult<bool>> + Send {
+        HttpClient::unlock_sim(self, pin.to_string())
+    }
+
+    fn restart_modem(&self) -> impl Future<Output = HttpResult<ModemRestartResponse>> + Send {
+        HttpClient::restart_modem(self)
+    }
+
+    fn get_device_info(&self) -> impl Future<Output = HttpResult<HttpSmsDeviceInfoData>> + Send {
+        HttpClient::get_device_info(self)
+    }
+
+    fn get_phone_number(&self) -> impl Future<Output = HttpResult<Option<String>>> + Send {
+        HttpClient::get_phone_number(self)
+    }
+
+    fn get_version(&self) -> impl Future<Output = HttpResult<String>> + Send {
+        HttpClient::get_version(self)
+    }
+
+    fn get_capabilities(&self) -> impl Future<Output = HttpResult<ServerCapabilities>> + Send {
+        HttpClient::get_capabilities(self)
+    }
+}
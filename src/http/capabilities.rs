@@ -0,0 +1,57 @@
+//! Server capability detection, from the feature names in the SMS-API version string.
+
+/// Optional SMS-API features, parsed from the version build metadata, eg: "0.0.1+sentry".
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ServerCapabilities {
+    /// The full version string reported by the server.
+    pub version: String,
+
+    /// Every feature name in the version build metadata.
+    pub features: Vec<String>,
+}
+impl ServerCapabilities {
+    /// Parse capabilities from an SMS-API version string. Feature names follow the
+    /// first `+`, separated by `+`, `.` or `,`.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::http::capabilities::ServerCapabilities;
+    ///
+    /// let capabilities = ServerCapabilities::from_version("0.0.1+sentry");
+    /// assert!(capabilities.has_feature("sentry"));
+    /// assert!(!capabilities.supports_mms());
+    /// ```
+    #[must_use]
+    pub fn from_version(version: &str) -> Self {
+        let features = version
+            .split_once('+')
+            .map(|(_, metadata)| {
+                metadata
+                    .split(['+', '.', ','])
+                    .map(str::trim)
+                    .filter(|feature| !feature.is_empty())
+                    .map(str::to_ascii_lowercase)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            version: version.to_string(),
+            features,
+        }
+    }
+
+    /// Check if the server was built with a named feature (case-insensitive).
+    #[must_use]
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features
+            .iter()
+            .any(|f| f.eq_ignore_ascii_case(feature))
+    }
+
+    /// Check if the server can send MMS messages.
+    #[must_use]
+    pub fn supports_mms(&self) -> bool {
+        self.has_feature("mms")
+    }
+}
@@ -0,0 +1,187 @@
+//! Circuit breaker that fast-fails requests while the SMS-API is unreachable.
+
+use crate::http::error::{HttpError, HttpResult};
+
+#[derive(Clone, Copy)]
+enum BreakerState {
+    /// Requests are allowed, counting consecutive failures.
+    Closed { failures: u32 },
+
+    /// Requests fail fast until the open duration has elapsed.
+    Open { until: tokio::time::Instant },
+
+    /// A single probe request is in flight, others fail fast until it completes.
+    HalfOpen { probe_started: tokio::time::Instant },
+}
+
+/// Opens after a number of consecutive failures, then allows a single probe
+/// request through once the open duration has elapsed. A successful probe closes it.
+pub(crate) struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: std::time::Duration,
+    state: std::sync::Mutex<BreakerState>,
+}
+impl CircuitBreaker {
+    /// Create a closed circuit breaker. A zero threshold is treated as one.
+    pub(crate) fn new(config: crate::config::HttpCircuitBreaker) -> Self {
+        Self {
+            failure_threshold: config.failure_threshold.max(1),
+            open_duration: config.open_duration,
+            state: std::sync::Mutex::new(BreakerState::Closed { failures: 0 }),
+        }
+    }
+
+    /// Check if a request may be sent, returning `HttpError::CircuitOpen` if not.
+    pub(crate) fn try_acquire(&self) -> HttpResult<()> {
+        let mut state = self.lock();
+        let now = tokio::time::Instant::now();
+
+        match *state {
+            BreakerState::Closed { .. } => Ok(()),
+            BreakerState::Open { until } if now >= until => {
+                *state = BreakerState::HalfOpen { probe_started: now };
+                Ok(())
+            }
+
+            // If a probe was abandoned (eg: its future was dropped), allow another.
+            BreakerState::HalfOpen { probe_started }
+                if now.duration_since(probe_started) >= self.open_duration =>
+            {
+                *state = BreakerState::HalfOpen { probe_started: now };
+                Ok(())
+            }
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => {
+                Err(HttpError::CircuitOpen)
+            }
+        }
+    }
+
+    /// Record the outcome of a request that was allowed through.
+    pub(crate) fn record(&self, success: bool) {
+        let mut state = self.lock();
+        let open = BreakerState::Open {
+            until: tokio::time::Instant::now() + self.open_duration,
+        };
+
+        *state = match (success, *state) {
+            (true, _) => BreakerState::Closed { failures: 0 },
+            (false, BreakerState::Closed { failures }) => {
+                let failures = failures.saturating_add(1);
+                if failures >= self.failure_threshold {
+                    open
+                } else {
+                    BreakerState::Closed { failures }
+                }
+            }
+            (false, BreakerState::Open { .. } | BreakerState::HalfOpen { .. }) => open,
+        };
+    }
+
+    /// Check if the circuit is currently failing fast.
+    pub(crate) fn is_open(&self) -> bool {
+        !matches!(*self.lock(), BreakerState::Closed { .. })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+impl std::fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("failure_threshold", &self.failure_threshold)
+            .field("open_duration", &self.open_duration)
+            .field("is_open", &self.is_open())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(failure_threshold: u32, open_duration: std::time::Duration) -> CircuitBreaker {
+        CircuitBreaker::new(crate::config::HttpCircuitBreaker {
+            failure_threshold,
+            open_duration,
+        })
+    }
+
+    const OPEN_DURATION: std::time::Duration = std::time::Duration::from_secs(30);
+
+    #[tokio::test(start_paused = true)]
+    async fn opens_after_consecutive_failures() {
+        let breaker = breaker(3, OPEN_DURATION);
+        breaker.record(false);
+        breaker.record(false);
+        assert!(!breaker.is_open());
+        assert!(breaker.try_acquire().is_ok());
+
+        breaker.record(false);
+        assert!(breaker.is_open());
+        assert!(matches!(breaker.try_acquire(), Err(HttpError::CircuitOpen)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn success_resets_the_failure_count() {
+        let breaker = breaker(2, OPEN_DURATION);
+        breaker.record(false);
+        breaker.record(true);
+        breaker.record(false);
+        assert!(!breaker.is_open());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn half_open_allows_a_single_probe() {
+        let breaker = breaker(1, OPEN_DURATION);
+        breaker.record(false);
+
+        tokio::time::advance(OPEN_DURATION - std::time::Duration::from_millis(1)).await;
+        assert!(breaker.try_acquire().is_err());
+
+        tokio::time::advance(std::time::Duration::from_millis(1)).await;
+        assert!(breaker.try_acquire().is_ok());
+        assert!(breaker.is_open());
+        assert!(breaker.try_acquire().is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn successful_probe_closes() {
+        let breaker = breaker(1, OPEN_DURATION);
+        breaker.record(false);
+        tokio::time::advance(OPEN_DURATION).await;
+        assert!(breaker.try_acquire().is_ok());
+
+        breaker.record(true);
+        assert!(!breaker.is_open());
+        assert!(breaker.try_acquire().is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failed_probe_reopens() {
+        let breaker = breaker(1, OPEN_DURATION);
+        breaker.record(false);
+        tokio::time::advance(OPEN_DURATION).await;
+        assert!(breaker.try_acquire().is_ok());
+
+        breaker.record(false);
+        assert!(matches!(breaker.try_acquire(), Err(HttpError::CircuitOpen)));
+        tokio::time::advance(OPEN_DURATION).await;
+        assert!(breaker.try_acquire().is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn abandoned_probe_is_replaced() {
+        let breaker = breaker(1, OPEN_DURATION);
+        breaker.record(false);
+        tokio::time::advance(OPEN_DURATION).await;
+        assert!(breaker.try_acquire().is_ok());
+
+        // The probe never records an outcome, so another is allowed after the open duration.
+        tokio::time::advance(OPEN_DURATION).await;
+        assert!(breaker.try_acquire().is_ok());
+    }
+}
@@ -0,0 +1,51 @@
+//! Client-side pre-flight checks for outgoing messages, without transmitting anything.
+
+/// A reason a message would not be sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SmsDryRunProblem {
+    /// The recipient is not a plausible phone number (optional `+` followed by 3 to 15 digits).
+    InvalidPhoneNumber(String),
+
+    /// The message content is empty.
+    EmptyContent,
+
+    /// The roaming policy is enabled and the SIM is roaming.
+    Roaming,
+
+    /// The circuit breaker is open, so the request would fail fast.
+    CircuitOpen,
+}
+impl std::fmt::Display for SmsDryRunProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPhoneNumber(number) => write!(f, "Invalid phone number: {number}"),
+            Self::EmptyContent => write!(f, "Message content is empty"),
+            Self::Roaming => write!(f, "Sending is blocked while the SIM is roaming"),
+            Self::CircuitOpen => write!(f, "Circuit breaker is open"),
+        }
+    }
+}
+
+/// The outcome of a dry-run send: what would happen if the message was sent.
+#[derive(Debug, Clone)]
+pub struct SmsDryRunReport {
+    /// Every problem found. The message would only be sent if this is empty.
+    pub problems: Vec<SmsDryRunProblem>,
+
+    /// The estimated encoding and number of segments the content will use.
+    pub encoding: crate::types::sms_encoding::SmsEncodingInfo,
+}
+impl SmsDryRunReport {
+    /// Check if the message would be handed to the server.
+    #[must_use]
+    pub fn would_send(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Check if a phone number is plausible, an optional `+` followed by 3 to 15 digits.
+#[must_use]
+pub fn is_valid_phone_number(phone_number: &str) -> bool {
+    let digits = phone_number.strip_prefix('+').unwrap_or(phone_number);
+    (3..=15).contains(&digits.len()) && digits.bytes().all(|b| b.is_ascii_digit())
+}
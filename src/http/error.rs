@@ -1,7 +1,9 @@
 //! HTTP interface related errors.
 
 /// An error originating from the SMS `HttpClient`.
+/// New variants may be added in minor releases, so matches need a wildcard arm.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum HttpError {
     /// Network request failed (connection issues, timeouts, etc.)
     #[error("Reqwest failure: {0}")]
@@ -26,11 +28,30 @@ pub enum HttpError {
         status: u16,
         /// Full response body as text.
         message: String,
+        /// How long the server asked to wait before retrying, from a `Retry-After` header.
+        retry_after: Option<std::time::Duration>,
     },
 
     /// API returned success=false with an error message.
-    #[error("{0}")]
-    ApiError(String),
+    #[error("{message}")]
+    ApiError {
+        /// The error message returned by the API.
+        message: String,
+        /// The machine-readable error code, if the API returned one.
+        code: Option<ApiErrorCode>,
+    },
+
+    /// The circuit breaker is open after repeated failures, so the request was not sent.
+    #[error("Circuit breaker is open, the SMS-API is considered unavailable")]
+    CircuitOpen,
+
+    /// The maximum number of requests were already in flight, and the client is set to fail fast.
+    #[error("Too many requests are already in flight")]
+    TooManyInFlight,
+
+    /// Sending was blocked by the roaming policy, as the SIM is roaming.
+    #[error("Sending is blocked while the SIM is roaming")]
+    Roaming,
 
     /// TLS configuration error
     #[error("TLS error: {0}")]
@@ -58,6 +79,35 @@ pub enum HttpError {
     },
 }
 impl HttpError {
+    /// Create an `ApiError` without an error code.
+    pub(crate) fn api(message: impl Into<String>) -> Self {
+        Self::ApiError {
+            message: message.into(),
+            code: None,
+        }
+    }
+
+    /// Get the API error code, if this is an `ApiError` with one.
+    #[must_use]
+    pub fn api_error_code(&self) -> Option<&ApiErrorCode> {
+        match self {
+            Self::ApiError { code, .. } => code.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Get how long the server asked to wait before retrying, if this is a rate limited
+    /// (429) or unavailable (503) `HttpStatus` error with a `Retry-After` header.
+    /// Requests are never retried automatically, but after a 429 the client holds every
+    /// request until the delay has passed, so a caller can simply retry.
+    #[must_use]
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            Self::HttpStatus { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
     fn status_text(status: u16) -> &'static str {
         match status {
             200 => "OK",
@@ -86,5 +136,82 @@ impl HttpError {
     }
 }
 
+/// A machine-readable error code returned by the API alongside its error message.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum ApiErrorCode {
+    /// The phone number is not valid.
+    InvalidNumber,
+
+    /// The request body or parameters are not valid.
+    InvalidRequest,
+
+    /// The modem is not connected or not responding.
+    ModemOffline,
+
+    /// The SIM is locked and must be unlocked with its PIN.
+    SimLocked,
+
+    /// The authorization token is missing or incorrect.
+    Unauthorized,
+
+    /// Too many requests were sent, and this one was rejected.
+    RateLimited,
+
+    /// The requested resource doesn't exist.
+    NotFound,
+
+    /// Any other error code returned by the API.
+    Other(String),
+}
+impl ApiErrorCode {
+    /// Get the error code as sent by the API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::InvalidNumber => "invalid_number",
+            Self::InvalidRequest => "invalid_request",
+            Self::ModemOffline => "modem_offline",
+            Self::SimLocked => "sim_locked",
+            Self::Unauthorized => "unauthorized",
+            Self::RateLimited => "rate_limited",
+            Self::NotFound => "not_found",
+            Self::Other(code) => code,
+        }
+    }
+}
+impl From<&str> for ApiErrorCode {
+    fn from(value: &str) -> Self {
+        match value.to_ascii_lowercase().as_str() {
+            "invalid_number" => Self::InvalidNumber,
+            "invalid_request" => Self::InvalidRequest,
+            "modem_offline" => Self::ModemOffline,
+            "sim_locked" => Self::SimLocked,
+            "unauthorized" => Self::Unauthorized,
+            "rate_limited" => Self::RateLimited,
+            "not_found" => Self::NotFound,
+            _ => Self::Other(value.to_string()),
+        }
+    }
+}
+impl From<String> for ApiErrorCode {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+impl From<ApiErrorCode> for String {
+    fn from(value: ApiErrorCode) -> Self {
+        match value {
+            ApiErrorCode::Other(code) => code,
+            code => code.as_str().to_string(),
+        }
+    }
+}
+impl std::fmt::Display for ApiErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Result type alias for HTTP operations.
 pub type HttpResult<T> = Result<T, HttpError>;
@@ -0,0 +1,217 @@
+//! Send groups, tracking a multi-recipient or multi-part logical message to completion.
+
+use crate::http::HttpClient;
+use crate::http::error::HttpResult;
+use crate::types::delivery::{DeliveryReportExt, DeliveryStatusGroup};
+use sms_types::sms::SmsOutgoingMessage;
+
+/// A single message within a `SendGroup`.
+#[derive(Clone, Debug)]
+pub struct SendGroupMember {
+    /// The message to send.
+    pub message: SmsOutgoingMessage,
+
+    /// The server message ID, once the message has been accepted.
+    pub message_id: Option<i64>,
+
+    /// The error from the last send attempt, if it failed.
+    pub send_error: Option<String>,
+
+    /// The latest delivery status, once a delivery report has been received.
+    pub delivery: Option<DeliveryStatusGroup>,
+
+    /// Key/value annotations for attributing the send, eg: a campaign name. These are kept
+    /// locally with the member, as the server does not store message metadata.
+    pub metadata: std::collections::BTreeMap<String, String>,
+}
+impl SendGroupMember {
+    /// Check if this member failed, either when sending or with a final failed delivery.
+    #[must_use]
+    pub fn is_failed(&self) -> bool {
+        self.send_error.is_some()
+            || self
+                .delivery
+                .is_some_and(|delivery| delivery.is_final() && !delivery.is_success())
+    }
+
+    /// Check if this member was delivered successfully.
+    #[must_use]
+    pub fn is_delivered(&self) -> bool {
+        self.delivery.is_some_and(DeliveryStatusGroup::is_success)
+    }
+}
+
+/// The overall status of a `SendGroup`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SendGroupStatus {
+    /// Some members are still being sent or awaiting a final delivery report.
+    Pending,
+
+    /// Every member was delivered.
+    Completed,
+
+    /// Every member has a final outcome, but some of them failed.
+    PartialFailure,
+}
+
+/// A group of messages tracked as one logical send. Failed members can be
+/// resent on their own without resending the members that were delivered.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use sms_client::http::HttpClient;
+/// use sms_client::http::group::{SendGroup, SendGroupStatus};
+/// use sms_client::types::sms::SmsOutgoingMessage;
+///
+/// async fn announce(http: &HttpClient) {
+///     let mut group = SendGroup::new(vec![
+///         SmsOutgoingMessage::simple_message("+447700900000", "Meeting moved to 3pm"),
+///         SmsOutgoingMessage::simple_message("+447700900001", "Meeting moved to 3pm"),
+///     ]);
+///
+///     group.send(http).await;
+///     while group.status() == SendGroupStatus::Pending {
+///         tokio::time::sleep(Duration::from_secs(5)).await;
+///         let _ = group.refresh(http).await;
+///     }
+///
+///     if group.status() == SendGroupStatus::PartialFailure {
+///         group.resend_failed(http).await;
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SendGroup {
+    members: Vec<SendGroupMember>,
+}
+impl SendGroup {
+    /// Create a group of unsent messages.
+    #[must_use]
+    pub fn new(messages: Vec<SmsOutgoingMessage>) -> Self {
+        Self {
+            members: messages
+                .into_iter()
+                .map(|message| SendGroupMember {
+                    message,
+                    message_id: None,
+                    send_error: None,
+                    delivery: None,
+                    metadata: std::collections::BTreeMap::new(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Annotate every member with a metadata key/value, replacing any existing value.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::http::group::SendGroup;
+    /// use sms_client::types::sms::SmsOutgoingMessage;
+    ///
+    /// let group = SendGroup::new(vec![
+    ///     SmsOutgoingMessage::simple_message("+447700900000", "Spring sale starts today!"),
+    /// ])
+    /// .with_metadata("campaign", "spring");
+    ///
+    /// for member in group.members() {
+    ///     assert_eq!(member.metadata.get("campaign").map(String::as_str), Some("spring"));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let key = key.into();
+        let value = value.into();
+        for member in &mut self.members {
+            member.metadata.insert(key.clone(), value.clone());
+        }
+        self
+    }
+
+    /// Find the member for a server message ID, eg: to attribute a delivery report.
+    #[must_use]
+    pub fn member_by_id(&self, message_id: i64) -> Option<&SendGroupMember> {
+        self.members
+            .iter()
+            .find(|member| member.message_id == Some(message_id))
+    }
+
+    /// Send every member that has not been accepted by the server yet.
+    pub async fn send(&mut self, http: &HttpClient) {
+        for member in &mut self.members {
+            if member.message_id.is_some() {
+                continue;
+            }
+
+            match http.send_sms(&member.message).await {
+                Ok(response) => {
+                    member.message_id = Some(response.message_id);
+                    member.send_error = None;
+                }
+                Err(e) => member.send_error = Some(e.to_string()),
+            }
+        }
+    }
+
+    /// Request delivery reports for every sent member without a final delivery status.
+    pub async fn refresh(&mut self, http: &HttpClient) -> HttpResult<()> {
+        for member in &mut self.members {
+            let Some(message_id) = member.message_id else {
+                continue;
+            };
+            if member.delivery.is_some_and(DeliveryStatusGroup::is_final) {
+                continue;
+            }
+
+            let reports = http.get_delivery_reports(message_id, None).await?;
+            if let Some(report) = reports.last() {
+                let group = report.status_group();
+                if group.is_final() {
+                    http.counters().record_delivery(group);
+                }
+                member.delivery = Some(group);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reset and resend only the failed members, leaving delivered members untouched.
+    pub async fn resend_failed(&mut self, http: &HttpClient) {
+        for member in &mut self.members {
+            if member.is_failed() {
+                member.message_id = None;
+                member.send_error = None;
+                member.delivery = None;
+            }
+        }
+        self.send(http).await;
+    }
+
+    /// Get the overall group status.
+    #[must_use]
+    pub fn status(&self) -> SendGroupStatus {
+        if self.members.iter().all(SendGroupMember::is_delivered) {
+            SendGroupStatus::Completed
+        } else if self
+            .members
+            .iter()
+            .all(|member| member.is_failed() || member.is_delivered())
+        {
+            SendGroupStatus::PartialFailure
+        } else {
+            SendGroupStatus::Pending
+        }
+    }
+
+    /// Get every member of the group.
+    #[must_use]
+    pub fn members(&self) -> &[SendGroupMember] {
+        &self.members
+    }
+
+    /// Get the members that failed to send or be delivered.
+    pub fn failed(&self) -> impl Iterator<Item = &SendGroupMember> {
+        self.members.iter().filter(|member| member.is_failed())
+    }
+}
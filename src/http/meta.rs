@@ -0,0 +1,56 @@
+//! Response metadata captured from HTTP headers, for diagnostics.
+
+/// The header carrying the server version.
+pub const SERVER_VERSION_HEADER: &str = "server";
+
+/// The header carrying the number of requests remaining in the current rate limit window.
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+
+/// The header carrying the server-assigned request ID.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Selected response headers, each None if the server didn't send it.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// The HTTP status code.
+    pub status: u16,
+
+    /// The server version, from the `server` header.
+    pub server_version: Option<String>,
+
+    /// Requests remaining in the current rate limit window, from `x-ratelimit-remaining`.
+    pub rate_limit_remaining: Option<u64>,
+
+    /// The request ID, from `x-request-id`. This is useful to correlate with server logs.
+    pub request_id: Option<String>,
+}
+impl ResponseMeta {
+    /// Capture the metadata from a response, before its body is read.
+    pub(crate) fn from_response(response: &reqwest::Response) -> Self {
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+
+        Self {
+            status: response.status().as_u16(),
+            server_version: header(SERVER_VERSION_HEADER),
+            rate_limit_remaining: header(RATE_LIMIT_REMAINING_HEADER)
+                .and_then(|value| value.trim().parse().ok()),
+            request_id: header(REQUEST_ID_HEADER),
+        }
+    }
+}
+
+/// A successful result alongside the response metadata.
+#[derive(Clone, Debug)]
+pub struct WithMeta<T> {
+    /// The result value.
+    pub value: T,
+
+    /// Metadata from the response headers.
+    pub meta: ResponseMeta,
+}
@@ -0,0 +1,74 @@
+//! Request/response hooks, for adding headers, signing or logging without forking the client.
+
+/// An interceptor applied to every request sent by an `HttpClient`.
+///
+/// # Example
+/// ```
+/// use sms_client::config::HttpConfig;
+/// use sms_client::http::middleware::HttpMiddleware;
+///
+/// struct TenantHeader(&'static str);
+/// impl HttpMiddleware for TenantHeader {
+///     fn before(&self, mut request: reqwest::Request) -> reqwest::Request {
+///         if let Ok(value) = self.0.parse() {
+///             request.headers_mut().insert("x-tenant", value);
+///         }
+///         request
+///     }
+///
+///     fn after(&self, response: &reqwest::Response) {
+///         println!("{} -> {}", response.url(), response.status());
+///     }
+/// }
+///
+/// let config = HttpConfig::new("http://192.168.1.2:3000").with_middleware(TenantHeader("home"));
+/// ```
+pub trait HttpMiddleware: Send + Sync {
+    /// Modify a request before it is sent. The default returns it unchanged.
+    fn before(&self, request: reqwest::Request) -> reqwest::Request {
+        request
+    }
+
+    /// Inspect a response once it has been received. The default does nothing.
+    fn after(&self, response: &reqwest::Response) {
+        let _ = response;
+    }
+}
+
+/// An ordered list of middleware, cheap to clone.
+/// `before` hooks run in the order they were added, and `after` hooks in reverse.
+#[derive(Clone, Default)]
+pub struct HttpMiddlewareStack(Vec<std::sync::Arc<dyn HttpMiddleware>>);
+impl HttpMiddlewareStack {
+    /// Add a middleware to the end of the stack.
+    pub fn push(&mut self, middleware: impl HttpMiddleware + 'static) {
+        self.0.push(std::sync::Arc::new(middleware));
+    }
+
+    /// Check if the stack has no middleware.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Apply every `before` hook to a request.
+    pub(crate) fn before(&self, request: reqwest::Request) -> reqwest::Request {
+        self.0
+            .iter()
+            .fold(request, |request, middleware| middleware.before(request))
+    }
+
+    /// Apply every `after` hook to a response.
+    pub(crate) fn after(&self, response: &reqwest::Response) {
+        for middleware in self.0.iter().rev() {
+            middleware.after(response);
+        }
+    }
+}
+impl std::fmt::Debug for HttpMiddlewareStack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpMiddlewareStack")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
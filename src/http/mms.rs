@@ -0,0 +1,122 @@
+//! Outgoing MMS messages, with a link-carrying SMS fallback for servers without MMS support.
+
+use sms_types::http::HttpSmsSendResponse;
+use sms_types::sms::SmsOutgoingMessage;
+
+/// A media attachment within an MMS message, referenced by URL.
+#[derive(serde::Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct MmsMediaPart {
+    /// The MIME type of the media, eg: "image/jpeg".
+    pub content_type: String,
+
+    /// Where the media can be fetched from. This is also the link sent in an SMS fallback.
+    pub url: String,
+}
+impl MmsMediaPart {
+    /// Create a media part from its MIME type and URL.
+    pub fn new(content_type: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            content_type: content_type.into(),
+            url: url.into(),
+        }
+    }
+}
+
+/// An outgoing MMS message, with an optional subject, text and media parts.
+///
+/// # Example
+/// ```
+/// use sms_client::http::mms::{HttpOutgoingMmsMessage, MmsMediaPart};
+///
+/// let message = HttpOutgoingMmsMessage::new("+447700900000")
+///     .with_subject("Site photo")
+///     .with_text("Taken this morning")
+///     .with_part(MmsMediaPart::new("image/jpeg", "https://example.com/site.jpg"));
+///
+/// let fallback = message.to_fallback_sms();
+/// assert!(fallback.content.contains("https://example.com/site.jpg"));
+/// ```
+#[derive(serde::Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct HttpOutgoingMmsMessage {
+    /// The recipient phone number.
+    pub to: String,
+
+    /// An optional message subject.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+
+    /// Optional text content, sent alongside the media parts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /// The media attachments.
+    pub parts: Vec<MmsMediaPart>,
+}
+impl HttpOutgoingMmsMessage {
+    /// Create an empty MMS message for a recipient.
+    pub fn new(to: impl Into<String>) -> Self {
+        Self {
+            to: to.into(),
+            subject: None,
+            text: None,
+            parts: Vec::new(),
+        }
+    }
+
+    /// Set the message subject.
+    #[must_use]
+    pub fn with_subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    /// Set the text content.
+    #[must_use]
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Add a media attachment.
+    #[must_use]
+    pub fn with_part(mut self, part: MmsMediaPart) -> Self {
+        self.parts.push(part);
+        self
+    }
+
+    /// Build the SMS sent in place of this message when MMS is unsupported.
+    /// It contains the subject, text and then a link for each media part, one per line.
+    #[must_use]
+    pub fn to_fallback_sms(&self) -> SmsOutgoingMessage {
+        let content = self
+            .subject
+            .iter()
+            .chain(&self.text)
+            .map(String::as_str)
+            .chain(self.parts.iter().map(|part| part.url.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        SmsOutgoingMessage::simple_message(&self.to, &content)
+    }
+}
+
+/// How an MMS message was sent.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MmsSendResult {
+    /// The message was sent as an MMS.
+    Mms(HttpSmsSendResponse),
+
+    /// The server does not support MMS, so the fallback SMS was sent instead.
+    SmsFallback(HttpSmsSendResponse),
+}
+impl MmsSendResult {
+    /// Get the send response, however the message was sent.
+    #[must_use]
+    pub fn response(&self) -> &HttpSmsSendResponse {
+        match self {
+            Self::Mms(response) | Self::SmsFallback(response) => response,
+        }
+    }
+}
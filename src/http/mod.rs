@@ -2,6 +2,7 @@
 //! This can be used to interface with the HTTP API standalone if required.
 
 use crate::http::error::{HttpError, HttpResult};
+use crate::types::modem::{ModemRestartResponse, SimPinStatus};
 use sms_types::gnss::{FixStatus, PositionReport};
 use sms_types::http::{
     HttpModemBatteryLevelResponse, HttpModemNetworkOperatorResponse,
@@ -11,8 +12,23 @@ use sms_types::http::{
 };
 use sms_types::sms::{SmsDeliveryReport, SmsOutgoingMessage};
 
+pub mod api;
+pub mod capabilities;
+pub mod dry_run;
 pub mod error;
+pub mod group;
+pub mod meta;
+pub mod middleware;
+pub mod mms;
+pub mod modem_cache;
 pub mod paginator;
+pub mod poll;
+pub mod roaming;
+
+mod circuit_breaker;
+mod rate_limit;
+
+pub use api::SmsApi;
 
 /// Take a response from the client, verify that the status code is 200,
 /// then read JSON body and ensure success is true and finally return response value.
@@ -26,9 +42,26 @@ where
         .and_then(|ct| ct.to_str().ok())
         .is_some_and(|ct| ct.contains("application/json"));
 
+    // Rate limiting is reported as a status error whatever the body, so the delay is kept.
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = read_retry_after(&response);
+        let body = response.text().await.unwrap_or_default();
+        let message = crate::json::from_slice::<serde_json::Value>(body.as_bytes())
+            .ok()
+            .and_then(|json| json.get("error")?.as_str().map(str::to_string))
+            .unwrap_or(body);
+
+        return Err(HttpError::HttpStatus {
+            status: 429,
+            message,
+            retry_after,
+        });
+    }
+
     if is_json {
         // Verify JSON success status.
-        let json: serde_json::Value = response.json().await?;
+        let body = response.bytes().await?;
+        let json: serde_json::Value = crate::json::from_slice(&body)?;
         let success = json
             .get("success")
             .and_then(serde_json::Value::as_bool)
@@ -40,8 +73,12 @@ where
                 .and_then(|v| v.as_str())
                 .unwrap_or("Unknown API error!")
                 .to_string();
+            let code = json
+                .get("error_code")
+                .and_then(|v| v.as_str())
+                .map(crate::http::error::ApiErrorCode::from);
 
-            return Err(HttpError::ApiError(message));
+            return Err(HttpError::ApiError { message, code });
         }
 
         // Read response field and make into expected value.
@@ -55,6 +92,7 @@ where
     // Return a status error if there isn't any JSON error to use.
     let status = response.status();
     if !status.is_success() {
+        let retry_after = read_retry_after(&response);
         let error_text = response
             .text()
             .await
@@ -63,12 +101,26 @@ where
         return Err(HttpError::HttpStatus {
             status: status.as_u16(),
             message: error_text,
+            retry_after,
         });
     }
 
     Err(HttpError::MissingResponseField)
 }
 
+/// Read a `Retry-After` header in delay-seconds form. HTTP-date values are ignored.
+fn read_retry_after(response: &reqwest::Response) -> Option<std::time::Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(std::time::Duration::from_secs)
+}
+
 /// Read a modem-specific response that contains a "type" field and "data" field.
 /// Verifies the type matches the expected type before returning the data.
 async fn read_modem_response<T>(expected: &str, response: reqwest::Response) -> HttpResult<T>
@@ -97,6 +149,53 @@ where
     serde_json::from_value(data.clone()).map_err(HttpError::JsonError)
 }
 
+/// Apply connection reuse, keep-alive and HTTP version options to a reqwest client.
+fn apply_connection_config(
+    builder: reqwest::ClientBuilder,
+    connection: &crate::config::HttpConnectionConfig,
+) -> reqwest::ClientBuilder {
+    let mut builder = builder;
+    if let Some(interval) = connection.tcp_keepalive {
+        builder = builder.tcp_keepalive(interval);
+    }
+    if let Some(timeout) = connection.pool_idle_timeout {
+        builder = builder.pool_idle_timeout(timeout);
+    }
+    if let Some(max_idle) = connection.pool_max_idle {
+        builder = builder.pool_max_idle_per_host(max_idle);
+    }
+
+    match connection.version {
+        crate::config::HttpVersionPreference::Negotiate => builder,
+        crate::config::HttpVersionPreference::Http1Only => builder.http1_only(),
+        #[cfg(feature = "http2")]
+        crate::config::HttpVersionPreference::Http2PriorKnowledge => {
+            builder.http2_prior_knowledge()
+        }
+    }
+}
+
+/// Set which compressed response encodings are accepted.
+#[cfg(feature = "compression")]
+fn apply_compression(
+    builder: reqwest::ClientBuilder,
+    compression: crate::config::HttpCompression,
+) -> reqwest::ClientBuilder {
+    builder
+        .gzip(compression.gzip)
+        .brotli(compression.brotli)
+        .deflate(compression.deflate)
+}
+
+/// Compressed responses are never requested without the `compression` feature.
+#[cfg(not(feature = "compression"))]
+fn apply_compression(
+    builder: reqwest::ClientBuilder,
+    _compression: crate::config::HttpCompression,
+) -> reqwest::ClientBuilder {
+    builder
+}
+
 /// Create a reqwest client with optional TLS configuration.
 fn client_builder(config: Option<&crate::config::TLSConfig>) -> HttpResult<reqwest::ClientBuilder> {
     let builder = reqwest::Client::builder();
@@ -114,6 +213,40 @@ fn client_builder(config: Option<&crate::config::TLSConfig>) -> HttpResult<reqwe
 
     #[cfg(any(feature = "http-tls-rustls", feature = "http-tls-native"))]
     {
+        // A pinned fingerprint is checked in place of, or in addition to, CA validation.
+        if let Some(fingerprint) = tls_config.pinned_sha256 {
+            #[cfg(feature = "http-tls-rustls")]
+            {
+                let root_certificate = tls_config
+                    .certificate
+                    .as_ref()
+                    .map(crate::tls::read_certificate)
+                    .transpose()
+                    .map_err(HttpError::TLSError)?;
+
+                let identity = tls_config
+                    .identity
+                    .as_ref()
+                    .map(crate::tls::read_identity)
+                    .transpose()
+                    .map_err(HttpError::TLSError)?;
+
+                let tls = crate::tls::pinned_client_config(fingerprint, root_certificate, identity)
+                    .map_err(|e| {
+                        HttpError::TLSError(format!("Failed to create pinned TLS config: {e}"))
+                    })?;
+                return Ok(builder.use_preconfigured_tls(tls));
+            }
+
+            #[cfg(not(feature = "http-tls-rustls"))]
+            {
+                let _ = fingerprint;
+                return Err(HttpError::TLSError(
+                    "Certificate pinning requires the 'http-tls-rustls' feature".to_string(),
+                ));
+            }
+        }
+
         let mut builder = builder;
 
         // Configure TLS backend
@@ -128,18 +261,28 @@ fn client_builder(config: Option<&crate::config::TLSConfig>) -> HttpResult<reqwe
         }
 
         // Load and add certificate
-        let certificate = load_certificate(&tls_config.certificate)?;
-        Ok(builder.add_root_certificate(certificate))
+        if let Some(certificate) = &tls_config.certificate {
+            builder = builder.add_root_certificate(load_certificate(certificate)?);
+        }
+        if let Some(identity) = &tls_config.identity {
+            builder = builder.identity(load_identity(identity)?);
+        }
+        if tls_config.danger_accept_invalid_certs {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        Ok(builder)
     }
 }
 
-/// Load a certificate filepath, returning the certificate set for builder.
+/// Load a certificate source, returning the certificate set for builder.
 #[cfg(any(feature = "http-tls-rustls", feature = "http-tls-native"))]
-fn load_certificate(cert_path: &std::path::Path) -> HttpResult<reqwest::tls::Certificate> {
-    let cert_data = std::fs::read(cert_path).map_err(HttpError::IOError)?;
+fn load_certificate(
+    source: &crate::config::CertificateSource,
+) -> HttpResult<reqwest::tls::Certificate> {
+    let (cert_data, ext) = source.read().map_err(HttpError::IOError)?;
 
-    // Try to parse based on file extension first
-    if let Some(ext) = cert_path.extension().and_then(|s| s.to_str()) {
+    // Try to parse based on the source format or file extension first
+    if let Some(ext) = ext {
         match ext {
             "pem" => return Ok(reqwest::tls::Certificate::from_pem(&cert_data)?),
             "der" => return Ok(reqwest::tls::Certificate::from_der(&cert_data)?),
@@ -160,13 +303,39 @@ fn load_certificate(cert_path: &std::path::Path) -> HttpResult<reqwest::tls::Cer
         .map_err(Into::into)
 }
 
+/// Load a client identity for mutual TLS from a PEM certificate chain and private key.
+#[cfg(any(feature = "http-tls-rustls", feature = "http-tls-native"))]
+fn load_identity(identity: &crate::config::ClientIdentity) -> HttpResult<reqwest::Identity> {
+    let certificate = std::fs::read(&identity.certificate).map_err(HttpError::IOError)?;
+    let key = std::fs::read(&identity.key).map_err(HttpError::IOError)?;
+
+    // native-tls requires a PKCS#8 key, rustls reads the key and chain from one PEM buffer.
+    #[cfg(feature = "http-tls-native")]
+    return Ok(reqwest::Identity::from_pkcs8_pem(&certificate, &key)?);
+
+    #[cfg(not(feature = "http-tls-native"))]
+    Ok(reqwest::Identity::from_pem(&[certificate, key].concat())?)
+}
+
 /// SMS-API HTTP interface client.
 #[derive(Debug)]
 pub struct HttpClient {
     base_url: reqwest::Url,
     authorization: Option<String>,
     modem_timeout: Option<std::time::Duration>,
+    sim_pin: Option<String>,
+    block_sends_while_roaming: bool,
     client: reqwest::Client,
+    state: crate::state::StateCache,
+    counters: crate::counters::Counters,
+    debug_dump: Option<crate::dump::DebugDump>,
+    rate_limiter: Option<rate_limit::RateLimiter>,
+    retry_after: rate_limit::RetryAfterGate,
+    circuit_breaker: Option<circuit_breaker::CircuitBreaker>,
+    request_slots: Option<tokio::sync::Semaphore>,
+    fail_fast_when_busy: bool,
+    middleware: middleware::HttpMiddlewareStack,
+    modem_cache: Option<modem_cache::ModemCache>,
 }
 impl HttpClient {
     /// Create a new HTTP client that uses the `base_url`.
@@ -174,16 +343,112 @@ impl HttpClient {
         config: crate::config::HttpConfig,
         tls: Option<&crate::config::TLSConfig>,
     ) -> HttpResult<Self> {
-        let client = client_builder(tls)?.timeout(config.base_timeout).build()?;
+        let builder = client_builder(tls)?.timeout(config.base_timeout);
+        let builder = apply_connection_config(builder, &config.connection);
+        let client = apply_compression(builder, config.compression).build()?;
+        Self::from_reqwest_with_config(client, config)
+    }
+
+    /// Create an HTTP client that reuses an existing `reqwest::Client` (eg: with custom TLS,
+    /// proxies or tuned pools), instead of constructing its own connection pool.
+    /// The provided client's own timeouts apply, as the default base timeout is set at build time.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::http::HttpClient;
+    ///
+    /// let shared = reqwest::Client::builder()
+    ///     .proxy(reqwest::Proxy::all("http://proxy.local:8080")?)
+    ///     .build()?;
+    ///
+    /// let http = HttpClient::from_reqwest(shared.clone(), "http://192.168.1.2:3000", Some("token!"))?;
+    /// ```
+    pub fn from_reqwest(
+        client: reqwest::Client,
+        base_url: impl Into<String>,
+        authorization: Option<impl Into<String>>,
+    ) -> HttpResult<Self> {
+        let mut config = crate::config::HttpConfig::new(base_url);
+        config.authorization = authorization.map(Into::into);
+        Self::from_reqwest_with_config(client, config)
+    }
 
+    /// Create an HTTP client that reuses an existing `reqwest::Client`, with the rest of the
+    /// configuration (modem timeout, rate limit, circuit breaker etc.) from an `HttpConfig`.
+    /// The config's `base_timeout`, `connection` and `compression` options are not applied, as they are set
+    /// when the provided client is built.
+    pub fn from_reqwest_with_config(
+        client: reqwest::Client,
+        config: crate::config::HttpConfig,
+    ) -> HttpResult<Self> {
         Ok(Self {
             base_url: reqwest::Url::parse(config.url.as_str())?,
             authorization: config.authorization,
             modem_timeout: config.modem_timeout,
+            sim_pin: config.sim_pin,
+            block_sends_while_roaming: config.block_sends_while_roaming,
             client,
+            state: crate::state::StateCache::default(),
+            counters: crate::counters::Counters::default(),
+            debug_dump: None,
+            rate_limiter: config.rate_limit.map(rate_limit::RateLimiter::new),
+            retry_after: rate_limit::RetryAfterGate::default(),
+            circuit_breaker: config
+                .circuit_breaker
+                .map(circuit_breaker::CircuitBreaker::new),
+            request_slots: config
+                .max_concurrent_requests
+                .map(|max| tokio::sync::Semaphore::new(max.max(1))),
+            fail_fast_when_busy: config.fail_fast_when_busy,
+            middleware: config.middleware,
+            modem_cache: config.modem_cache_ttl.map(modem_cache::ModemCache::new),
         })
     }
 
+    /// Get the SMS-API base URL requests are sent to.
+    #[must_use]
+    pub fn base_url(&self) -> &reqwest::Url {
+        &self.base_url
+    }
+
+    /// Record every request and response to a debug dump.
+    pub fn set_debug_dump(&mut self, dump: crate::dump::DebugDump) {
+        self.debug_dump = Some(dump);
+    }
+
+    /// Check if the last-known network status is roaming. This is false if the
+    /// network status has not been requested yet.
+    #[must_use]
+    pub fn is_roaming(&self) -> bool {
+        self.state.read(|state| {
+            state
+                .network_status
+                .as_ref()
+                .is_some_and(|status| roaming::is_roaming(&status.value))
+        })
+    }
+
+    /// Check if the circuit breaker is currently failing requests fast.
+    /// This is always false if no circuit breaker is configured.
+    #[must_use]
+    pub fn is_circuit_open(&self) -> bool {
+        self.circuit_breaker
+            .as_ref()
+            .is_some_and(circuit_breaker::CircuitBreaker::is_open)
+    }
+
+    /// Get the last-known state cache, which is updated by successful modem requests.
+    #[must_use]
+    pub fn state_cache(&self) -> &crate::state::StateCache {
+        &self.state
+    }
+
+    /// Get the cumulative counters, which are updated by sends and requests.
+    #[must_use]
+    pub fn counters(&self) -> &crate::counters::Counters {
+        &self.counters
+    }
+
     /// Set/Remove the friendly name for a given phone number.
     pub async fn set_friendly_name(
         &self,
@@ -196,11 +461,8 @@ impl HttpClient {
         });
 
         let url = self.base_url.join("/db/friendly-names/set")?;
-        let response = self
-            .setup_request(false, self.client.post(url))
-            .json(&body)
-            .send()
-            .await?;
+        let request = self.setup_request(false, self.client.post(url)).json(&body);
+        let response = self.send(request).await?;
 
         read_http_response(response).await
     }
@@ -215,11 +477,8 @@ impl HttpClient {
         });
 
         let url = self.base_url.join("/db/friendly-names/get")?;
-        let response = self
-            .setup_request(false, self.client.post(url))
-            .json(&body)
-            .send()
-            .await?;
+        let request = self.setup_request(false, self.client.post(url)).json(&body);
+        let response = self.send(request).await?;
 
         read_http_response(response).await
     }
@@ -239,11 +498,8 @@ impl HttpClient {
         }
 
         let url = self.base_url.join("/db/sms")?;
-        let response = self
-            .setup_request(false, self.client.post(url))
-            .json(&body)
-            .send()
-            .await?;
+        let request = self.setup_request(false, self.client.post(url)).json(&body);
+        let response = self.send(request).await?;
 
         read_http_response(response).await
     }
@@ -262,7 +518,7 @@ impl HttpClient {
             request = request.json(&pagination);
         }
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         read_http_response(response).await
     }
 
@@ -281,11 +537,8 @@ impl HttpClient {
         }
 
         let url = self.base_url.join("/db/delivery-reports")?;
-        let response = self
-            .setup_request(false, self.client.post(url))
-            .json(&body)
-            .send()
-            .await?;
+        let request = self.setup_request(false, self.client.post(url)).json(&body);
+        let response = self.send(request).await?;
 
         read_http_response(response).await
     }
@@ -294,6 +547,34 @@ impl HttpClient {
     /// message reference (provided from modem) and message id (used internally).
     /// This will use the message timeout for the request if one is set.
     pub async fn send_sms(&self, message: &SmsOutgoingMessage) -> HttpResult<HttpSmsSendResponse> {
+        self.send_sms_with_meta(message)
+            .await
+            .map(|result| result.value)
+    }
+
+    /// Send an SMS message like `send_sms`, also returning metadata from the response
+    /// headers (eg: the request ID and remaining rate limit) for diagnostics.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::http::HttpClient;
+    /// use sms_client::types::sms::SmsOutgoingMessage;
+    ///
+    /// async fn send(http: &HttpClient) {
+    ///     let message = SmsOutgoingMessage::simple_message("+447700900000", "Hello!");
+    ///     if let Ok(result) = http.send_sms_with_meta(&message).await {
+    ///         println!("Sent {} (request {:?})", result.value.message_id, result.meta.request_id);
+    ///     }
+    /// }
+    /// ```
+    pub async fn send_sms_with_meta(
+        &self,
+        message: &SmsOutgoingMessage,
+    ) -> HttpResult<meta::WithMeta<HttpSmsSendResponse>> {
+        if self.block_sends_while_roaming && self.is_roaming() {
+            return Err(HttpError::Roaming);
+        }
+
         let url = self.base_url.join("/sms/send")?;
 
         // Create request, applying request timeout if one is set (+ 5).
@@ -303,9 +584,67 @@ impl HttpClient {
             request = request.timeout(std::time::Duration::from_secs(u64::from(timeout) + 5));
         }
 
-        let response = request.json(message).send().await?;
+        let response = self.send(request.json(message)).await?;
+        let meta = meta::ResponseMeta::from_response(&response);
 
-        read_http_response(response).await
+        let value = read_http_response(response).await?;
+        self.counters.record_message_sent();
+        Ok(meta::WithMeta { value, meta })
+    }
+
+    /// Check what would happen if a message was sent, without transmitting anything.
+    /// This validates the recipient and content, and applies the client-side policies.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::http::HttpClient;
+    /// use sms_client::types::sms::SmsOutgoingMessage;
+    ///
+    /// fn preflight(http: &HttpClient) {
+    ///     let message = SmsOutgoingMessage::simple_message("+447700900000", "Hello!");
+    ///     let report = http.send_sms_dry_run(&message);
+    ///     for problem in &report.problems {
+    ///         println!("Would not send: {problem}");
+    ///     }
+    ///     println!("Would use {} segment(s)", report.encoding.segments);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn send_sms_dry_run(&self, message: &SmsOutgoingMessage) -> dry_run::SmsDryRunReport {
+        let mut problems = Vec::new();
+        if !dry_run::is_valid_phone_number(&message.to) {
+            problems.push(dry_run::SmsDryRunProblem::InvalidPhoneNumber(
+                message.to.clone(),
+            ));
+        }
+        if message.content.is_empty() {
+            problems.push(dry_run::SmsDryRunProblem::EmptyContent);
+        }
+        if self.block_sends_while_roaming && self.is_roaming() {
+            problems.push(dry_run::SmsDryRunProblem::Roaming);
+        }
+        if self.is_circuit_open() {
+            problems.push(dry_run::SmsDryRunProblem::CircuitOpen);
+        }
+
+        dry_run::SmsDryRunReport {
+            problems,
+            encoding: crate::types::sms_encoding::SmsEncodingInfo::analyze(&message.content),
+        }
+    }
+
+    /// Send a batch of SMS messages, returning a result for each message in the same order.
+    /// Messages are sent sequentially, as the modem can only send one at a time anyway,
+    /// so one failing message (eg: an invalid number) does not stop the rest of the batch.
+    pub async fn send_sms_batch(
+        &self,
+        messages: &[SmsOutgoingMessage],
+    ) -> Vec<HttpResult<HttpSmsSendResponse>> {
+        let mut results = Vec::with_capacity(messages.len());
+        for message in messages {
+            results.push(self.send_sms(message).await);
+        }
+        results
     }
 
     /// Get the carrier network status.
@@ -353,13 +692,117 @@ impl HttpClient {
         self.modem_request("/gnss/location", "GNSSLocation").await
     }
 
+    /// Power on the GNSS receiver. Returns true if the modem acknowledged the change.
+    pub async fn enable_gnss(&self) -> HttpResult<bool> {
+        self.set_gnss_power(true).await
+    }
+
+    /// Power off the GNSS receiver, eg: to save power while a tracker is parked.
+    /// Returns true if the modem acknowledged the change.
+    pub async fn disable_gnss(&self) -> HttpResult<bool> {
+        self.set_gnss_power(false).await
+    }
+
+    /// Set how often the GNSS receiver sends unsolicited position reports.
+    /// Returns true if the modem acknowledged the change.
+    pub async fn set_gnss_report_interval(
+        &self,
+        interval: std::time::Duration,
+    ) -> HttpResult<bool> {
+        let body = serde_json::json!({
+            "interval": interval.as_secs()
+        });
+        self.modem_command("/gnss/report-interval", "GNSSReportInterval", &body)
+            .await
+    }
+
+    /// Get the SIM lock state, indicating whether a PIN must be supplied.
+    pub async fn get_pin_status(&self) -> HttpResult<SimPinStatus> {
+        self.modem_request("/sms/pin-status", "PinStatus").await
+    }
+
+    /// Unlock the SIM with its PIN. Returns true if the modem accepted the PIN.
+    pub async fn unlock_sim(&self, pin: impl Into<String>) -> HttpResult<bool> {
+        let body = serde_json::json!({
+            "pin": pin.into()
+        });
+
+        let url = self.base_url.join("/sms/unlock-sim")?;
+        let request = self.setup_request(true, self.client.post(url)).json(&body);
+        let response = self.send(request).await?;
+
+        // The lock state is about to change, so a cached status would be wrong.
+        if let Some(cache) = &self.modem_cache {
+            cache.remove("/sms/pin-status");
+        }
+
+        read_http_response(response).await
+    }
+
+    /// Check if a SIM PIN is configured, to be supplied by `ensure_sim_unlocked`.
+    #[must_use]
+    pub fn has_sim_pin(&self) -> bool {
+        self.sim_pin.is_some()
+    }
+
+    /// Supply the configured SIM PIN if the SIM is waiting for one, returning the resulting
+    /// status. `Client` calls this on creation and after each modem status update when a PIN
+    /// is configured, removing the manual unlock step after a power cycle.
+    /// If no PIN is configured, the current status is returned as-is. The status is always
+    /// fetched from the modem, as a cached status may be from before a power cycle.
+    pub async fn ensure_sim_unlocked(&self) -> HttpResult<SimPinStatus> {
+        let status = self.fetch_pin_status().await?;
+        let Some(pin) = &self.sim_pin else {
+            return Ok(status);
+        };
+        if !status.needs_pin() {
+            return Ok(status);
+        }
+
+        if !self.unlock_sim(pin.as_str()).await? {
+            return Err(HttpError::api("The SIM PIN was rejected"));
+        }
+        self.fetch_pin_status().await
+    }
+
+    /// Discard the cached SIM lock state, eg: after the modem reports a status change.
+    pub(crate) fn forget_pin_status(&self) {
+        if let Some(cache) = &self.modem_cache {
+            cache.remove("/sms/pin-status");
+        }
+    }
+
+    /// Fetch the SIM lock state from the modem, bypassing and then refreshing the cache.
+    async fn fetch_pin_status(&self) -> HttpResult<SimPinStatus> {
+        let data = self
+            .fetch_modem_state("/sms/pin-status", "PinStatus")
+            .await?;
+        if let Some(cache) = &self.modem_cache {
+            cache.insert("/sms/pin-status", "PinStatus", data.clone());
+        }
+        serde_json::from_value(data).map_err(HttpError::JsonError)
+    }
+
+    /// Restart the modem, eg: to recover it from a wedged state. This uses the modem timeout,
+    /// and the modem will be unavailable for a short time after accepting the restart.
+    pub async fn restart_modem(&self) -> HttpResult<ModemRestartResponse> {
+        let response: ModemRestartResponse = self
+            .modem_command("/sms/restart-modem", "ModemRestart", &serde_json::json!({}))
+            .await?;
+
+        if response.accepted
+            && let Some(cache) = &self.modem_cache
+        {
+            cache.clear();
+        }
+        Ok(response)
+    }
+
     /// Get device info summary result. This is a more efficient way to request all device info.
     pub async fn get_device_info(&self) -> HttpResult<HttpSmsDeviceInfoData> {
         let url = self.base_url.join("/sms/device-info")?;
-        let response = self
-            .setup_request(true, self.client.get(url))
-            .send()
-            .await?;
+        let request = self.setup_request(true, self.client.get(url));
+        let response = self.send(request).await?;
 
         let response = read_http_response::<HttpSmsDeviceInfoResponse>(response).await?;
         Ok(HttpSmsDeviceInfoData::from(response))
@@ -369,10 +812,8 @@ impl HttpClient {
     /// This is optional, as the API could have left this un-configured without any value set.
     pub async fn get_phone_number(&self) -> HttpResult<Option<String>> {
         let url = self.base_url.join("/sys/phone-number")?;
-        let response = self
-            .setup_request(false, self.client.get(url))
-            .send()
-            .await?;
+        let request = self.setup_request(false, self.client.get(url));
+        let response = self.send(request).await?;
 
         read_http_response(response).await
     }
@@ -381,28 +822,321 @@ impl HttpClient {
     /// often with feature names added as a suffix, eg: "0.0.1+sentry".
     pub async fn get_version(&self) -> HttpResult<String> {
         let url = self.base_url.join("/sys/version")?;
-        let response = self
-            .setup_request(false, self.client.get(url))
-            .send()
-            .await?;
+        let request = self.setup_request(false, self.client.get(url));
+        let response = self.send(request).await?;
 
         read_http_response(response).await
     }
 
-    /// Send an SMS modem request, the response contains a named type which is verified.
-    async fn modem_request<T>(&self, route: &str, expected: &str) -> HttpResult<T>
+    /// Get the server capabilities, parsed from the feature names in its version string.
+    /// This can be cached by the caller, as it only changes when the server is updated.
+    pub async fn get_capabilities(&self) -> HttpResult<capabilities::ServerCapabilities> {
+        let version = self.get_version().await?;
+        Ok(capabilities::ServerCapabilities::from_version(&version))
+    }
+
+    /// Send an MMS message if the server supports it, otherwise send its fallback SMS
+    /// containing the subject, text and a link to each media part.
+    pub async fn send_mms(
+        &self,
+        message: &mms::HttpOutgoingMmsMessage,
+        capabilities: &capabilities::ServerCapabilities,
+    ) -> HttpResult<mms::MmsSendResult> {
+        if !capabilities.supports_mms() {
+            let fallback = message.to_fallback_sms();
+            return Ok(mms::MmsSendResult::SmsFallback(
+                self.send_sms(&fallback).await?,
+            ));
+        }
+        if self.block_sends_while_roaming && self.is_roaming() {
+            return Err(HttpError::Roaming);
+        }
+
+        let url = self.base_url.join("/mms/send")?;
+        let request = self.setup_request(true, self.client.post(url));
+        let response = self.send(request.json(message)).await?;
+
+        Ok(mms::MmsSendResult::Mms(read_http_response(response).await?))
+    }
+
+    /// Refetch every modem response, so the next requests and the last-known state return current values.
+    /// A failed route doesn't stop the others from refreshing, every failure is returned.
+    /// This does nothing if the modem cache is disabled.
+    pub async fn force_refresh(&self) -> Vec<modem_cache::ModemRefreshFailure> {
+        self.refresh_modem_routes(&modem_cache::MODEM_ROUTES).await
+    }
+
+    /// Refetch the modem responses that are already cached, for the background refresher.
+    pub(crate) async fn refresh_cached_routes(&self) -> Vec<modem_cache::ModemRefreshFailure> {
+        let routes = self
+            .modem_cache
+            .as_ref()
+            .map(modem_cache::ModemCache::routes)
+            .unwrap_or_default();
+        self.refresh_modem_routes(&routes).await
+    }
+
+    /// Get the modem cache TTL, if the cache is enabled.
+    pub(crate) fn modem_cache_ttl(&self) -> Option<std::time::Duration> {
+        self.modem_cache.as_ref().map(modem_cache::ModemCache::ttl)
+    }
+
+    async fn refresh_modem_routes(
+        &self,
+        routes: &[(&'static str, &'static str)],
+    ) -> Vec<modem_cache::ModemRefreshFailure> {
+        let mut failures = Vec::new();
+        let Some(cache) = &self.modem_cache else {
+            return failures;
+        };
+
+        for &(route, expected) in routes {
+            let _flight = cache.flight(route).await;
+            match self.fetch_modem_state(route, expected).await {
+                Ok(data) => cache.insert(route, expected, data),
+                Err(error) => failures.push(modem_cache::ModemRefreshFailure { route, error }),
+            }
+        }
+        failures
+    }
+
+    /// Switch the GNSS receiver power, discarding any cached GNSS responses.
+    async fn set_gnss_power(&self, enabled: bool) -> HttpResult<bool> {
+        let body = serde_json::json!({
+            "enabled": enabled
+        });
+        let acknowledged = self
+            .modem_command("/gnss/power", "GNSSPower", &body)
+            .await?;
+
+        if let Some(cache) = &self.modem_cache {
+            cache.remove("/gnss/status");
+            cache.remove("/gnss/location");
+        }
+        Ok(acknowledged)
+    }
+
+    /// Send an SMS modem command with a JSON body, the response contains a named type which is verified.
+    async fn modem_command<T>(
+        &self,
+        route: &str,
+        expected: &str,
+        body: &serde_json::Value,
+    ) -> HttpResult<T>
     where
         T: serde::de::DeserializeOwned,
     {
         let url = self.base_url.join(route)?;
-        let response = self
-            .setup_request(true, self.client.get(url))
-            .send()
-            .await?;
+        let request = self.setup_request(true, self.client.post(url)).json(body);
+        let response = self.send(request).await?;
 
         read_modem_response::<T>(expected, response).await
     }
 
+    /// Send an SMS modem request, the response contains a named type which is verified.
+    /// The response is reused from the modem cache if it is enabled and fresh, and concurrent
+    /// requests for the same route share a single modem request.
+    async fn modem_request<T>(&self, route: &'static str, expected: &'static str) -> HttpResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let data = match &self.modem_cache {
+            Some(cache) => match cache.get(route) {
+                Some(data) => data,
+                None => {
+                    // Check again once the route is free, another caller may have just fetched it.
+                    let _flight = cache.flight(route).await;
+                    match cache.get(route) {
+                        Some(data) => data,
+                        None => {
+                            let data = self.fetch_modem_state(route, expected).await?;
+                            cache.insert(route, expected, data.clone());
+                            data
+                        }
+                    }
+                }
+            },
+            None => self.fetch_modem_state(route, expected).await?,
+        };
+        serde_json::from_value(data).map_err(HttpError::JsonError)
+    }
+
+    /// Fetch a modem response and record it into the last-known state, the revision
+    /// is taken before the request so a newer WebSocket event is never overwritten.
+    /// Cached responses don't pass through here, so they never refresh the state.
+    async fn fetch_modem_state(
+        &self,
+        route: &'static str,
+        expected: &'static str,
+    ) -> HttpResult<serde_json::Value> {
+        let revision = self.state.next_revision();
+        let data = self.fetch_modem_data(route, expected).await?;
+        self.record_modem_state(route, revision, &data);
+        Ok(data)
+    }
+
+    /// Update the last-known state field that corresponds to a modem route, if any.
+    fn record_modem_state(&self, route: &str, revision: u64, data: &serde_json::Value) {
+        fn update<T>(
+            state: &crate::state::StateCache,
+            revision: u64,
+            data: &serde_json::Value,
+            field: impl FnOnce(
+                &mut crate::state::LastKnownState,
+            ) -> &mut Option<crate::state::Timestamped<T>>,
+        ) where
+            T: serde::de::DeserializeOwned,
+        {
+            if let Ok(value) = T::deserialize(data) {
+                state.update_newer(revision, field, value);
+            }
+        }
+
+        let state = &self.state;
+        match route {
+            "/sms/modem-status" => {
+                update::<HttpModemNetworkStatusResponse>(state, revision, data, |state| {
+                    &mut state.network_status
+                })
+            }
+            "/sms/signal-strength" => {
+                update::<HttpModemSignalStrengthResponse>(state, revision, data, |state| {
+                    &mut state.signal_strength
+                })
+            }
+            "/sms/network-operator" => {
+                update::<HttpModemNetworkOperatorResponse>(state, revision, data, |state| {
+                    &mut state.network_operator
+                })
+            }
+            "/sms/battery-level" => {
+                update::<HttpModemBatteryLevelResponse>(state, revision, data, |state| {
+                    &mut state.battery_level
+                })
+            }
+            "/gnss/status" => {
+                update::<FixStatus>(state, revision, data, |state| &mut state.gnss_status);
+            }
+            "/gnss/location" => {
+                update::<PositionReport>(state, revision, data, |state| &mut state.gnss_location);
+            }
+            _ => {}
+        }
+    }
+
+    /// Send an SMS modem request, returning the verified raw `data` value.
+    async fn fetch_modem_data(&self, route: &str, expected: &str) -> HttpResult<serde_json::Value> {
+        let url = self.base_url.join(route)?;
+        let request = self.setup_request(true, self.client.get(url));
+        let response = self.send(request).await?;
+
+        read_modem_response::<serde_json::Value>(expected, response).await
+    }
+
+    /// Build and send a prepared request, applying the circuit breaker and rate limiter
+    /// if they are configured.
+    async fn send(&self, builder: reqwest::RequestBuilder) -> HttpResult<reqwest::Response> {
+        let request = builder.build()?;
+
+        // Hold a request slot until the response has been received (the semaphore is never closed).
+        let _slot = match &self.request_slots {
+            Some(slots) if self.fail_fast_when_busy => match slots.try_acquire() {
+                Ok(permit) => Some(permit),
+                Err(tokio::sync::TryAcquireError::NoPermits) => {
+                    return Err(HttpError::TooManyInFlight);
+                }
+                Err(tokio::sync::TryAcquireError::Closed) => None,
+            },
+            Some(slots) => match slots.try_acquire() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    let started = std::time::Instant::now();
+                    let permit = slots.acquire().await.ok();
+                    self.counters.record_queue_wait(started.elapsed());
+                    permit
+                }
+            },
+            None => None,
+        };
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+        self.retry_after.wait().await;
+
+        // Check the breaker last, so a half-open probe is only claimed by a request that is
+        // sent straight away, and always has its outcome recorded.
+        if let Some(circuit_breaker) = &self.circuit_breaker {
+            circuit_breaker.try_acquire()?;
+        }
+
+        let result = self.execute(request).await;
+        self.counters.record_http_result(&result);
+
+        // Hold further requests for as long as the server asked when rate limited.
+        if let Ok(response) = &result
+            && response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+            && let Some(retry_after) = read_retry_after(response)
+        {
+            self.retry_after.pause_for(retry_after);
+        }
+        if let Some(circuit_breaker) = &self.circuit_breaker {
+            circuit_breaker.record(
+                result
+                    .as_ref()
+                    .is_ok_and(|response| !response.status().is_server_error()),
+            );
+        }
+        result
+    }
+
+    /// Execute a built request within a `tracing` span, recording the status and latency.
+    #[cfg(feature = "tracing")]
+    async fn execute(&self, request: reqwest::Request) -> HttpResult<reqwest::Response> {
+        use tracing::Instrument;
+
+        let span = tracing::debug_span!(
+            "sms_client.http",
+            method = %request.method(),
+            endpoint = request.url().path(),
+        );
+        let started = std::time::Instant::now();
+        let result = self.execute_request(request).instrument(span.clone()).await;
+
+        let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        match &result {
+            Ok(response) => tracing::debug!(
+                parent: &span,
+                status = response.status().as_u16(),
+                latency_ms,
+                "HTTP response received"
+            ),
+            Err(e) => tracing::warn!(parent: &span, error = %e, latency_ms, "HTTP request failed"),
+        }
+        result
+    }
+
+    /// Execute a built request.
+    #[cfg(not(feature = "tracing"))]
+    async fn execute(&self, request: reqwest::Request) -> HttpResult<reqwest::Response> {
+        self.execute_request(request).await
+    }
+
+    /// Execute a built request, applying the middleware and recording it to the debug dump if enabled.
+    async fn execute_request(&self, request: reqwest::Request) -> HttpResult<reqwest::Response> {
+        let request = self.middleware.before(request);
+        let response = match &self.debug_dump {
+            Some(dump) => {
+                dump.record_http_request(&request);
+                let response = self.client.execute(request).await?;
+                dump.record_http_response(response).await?
+            }
+            None => self.client.execute(request).await?,
+        };
+
+        self.middleware.after(&response);
+        Ok(response)
+    }
+
     /// Allow for a different timeout to be used for modem requests,
     /// and apply optional authorization header to request builder.
     fn setup_request(
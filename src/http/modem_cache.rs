@@ -0,0 +1,171 @@
+//! A TTL cache for slow modem requests, eg: signal strength and battery level.
+
+use crate::http::HttpClient;
+use crate::http::error::HttpError;
+
+/// Every cacheable modem route with its expected response type, refreshed by `force_refresh`.
+pub(crate) const MODEM_ROUTES: [(&str, &str); 8] = [
+    ("/sms/modem-status", "NetworkStatus"),
+    ("/sms/signal-strength", "SignalStrength"),
+    ("/sms/network-operator", "NetworkOperator"),
+    ("/sms/service-provider", "ServiceProvider"),
+    ("/sms/battery-level", "BatteryLevel"),
+    ("/sms/pin-status", "PinStatus"),
+    ("/gnss/status", "GNSSStatus"),
+    ("/gnss/location", "GNSSLocation"),
+];
+
+/// A modem route that couldn't be refreshed, returned by `HttpClient::force_refresh`.
+#[derive(Debug)]
+pub struct ModemRefreshFailure {
+    /// The modem route, eg: "/sms/signal-strength".
+    pub route: &'static str,
+
+    /// Why the route couldn't be fetched. Its previous cached value is kept until it expires.
+    pub error: HttpError,
+}
+
+/// A cached modem response, as the raw `data` value so any response type can share the cache.
+struct CacheEntry {
+    expected: &'static str,
+    fetched_at: tokio::time::Instant,
+    data: serde_json::Value,
+}
+
+/// Modem responses keyed by route, each reused until it is older than the TTL.
+pub(crate) struct ModemCache {
+    ttl: std::time::Duration,
+    entries: std::sync::Mutex<std::collections::HashMap<&'static str, CacheEntry>>,
+    flights: std::sync::Mutex<
+        std::collections::HashMap<&'static str, std::sync::Arc<tokio::sync::Mutex<()>>>,
+    >,
+}
+impl ModemCache {
+    pub(crate) fn new(ttl: std::time::Duration) -> Self {
+        Self {
+            ttl,
+            entries: std::sync::Mutex::default(),
+            flights: std::sync::Mutex::default(),
+        }
+    }
+
+    /// Get the cache TTL.
+    pub(crate) fn ttl(&self) -> std::time::Duration {
+        self.ttl
+    }
+
+    /// Wait for exclusive use of a route, so concurrent misses send a single modem request
+    /// and the rest reuse its response. The route is released when the guard is dropped.
+    pub(crate) async fn flight(&self, route: &'static str) -> tokio::sync::OwnedMutexGuard<()> {
+        let flight = {
+            let mut flights = match self.flights.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            std::sync::Arc::clone(flights.entry(route).or_default())
+        };
+        flight.lock_owned().await
+    }
+
+    /// Get the cached data for a route, if it is younger than the TTL.
+    pub(crate) fn get(&self, route: &str) -> Option<serde_json::Value> {
+        self.lock()
+            .get(route)
+            .filter(|entry| entry.fetched_at.elapsed() < self.ttl)
+            .map(|entry| entry.data.clone())
+    }
+
+    /// Store fresh data for a route.
+    pub(crate) fn insert(
+        &self,
+        route: &'static str,
+        expected: &'static str,
+        data: serde_json::Value,
+    ) {
+        self.lock().insert(
+            route,
+            CacheEntry {
+                expected,
+                fetched_at: tokio::time::Instant::now(),
+                data,
+            },
+        );
+    }
+
+    /// Remove a route, so its next request goes to the modem.
+    pub(crate) fn remove(&self, route: &str) {
+        self.lock().remove(route);
+    }
+
+    /// Remove every route, eg: after the modem restarts.
+    pub(crate) fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Get every cached route with its expected response type.
+    pub(crate) fn routes(&self) -> Vec<(&'static str, &'static str)> {
+        self.lock()
+            .iter()
+            .map(|(route, entry)| (*route, entry.expected))
+            .collect()
+    }
+
+    fn lock(
+        &self,
+    ) -> std::sync::MutexGuard<'_, std::collections::HashMap<&'static str, CacheEntry>> {
+        match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+impl std::fmt::Debug for ModemCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModemCache")
+            .field("ttl", &self.ttl)
+            .field("entries", &self.lock().len())
+            .finish()
+    }
+}
+
+/// Periodically refreshes every cached modem response, so callers rarely wait on the modem.
+/// `Client` starts one automatically when the modem cache is enabled.
+pub struct ModemCacheRefresher;
+impl ModemCacheRefresher {
+    /// Spawn the refresher task, refreshing each `interval` (usually a little under the
+    /// cache TTL). The task stops once the HTTP client is dropped, or when the returned
+    /// handle is aborted.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use sms_client::http::HttpClient;
+    /// use sms_client::http::modem_cache::ModemCacheRefresher;
+    ///
+    /// fn keep_fresh(http: Arc<HttpClient>) {
+    ///     ModemCacheRefresher::spawn(http, Duration::from_secs(50));
+    /// }
+    /// ```
+    pub fn spawn(
+        http: std::sync::Arc<HttpClient>,
+        interval: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        // Hold a weak reference, so the task doesn't keep the client alive.
+        let http = std::sync::Arc::downgrade(&http);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+                let Some(http) = http.upgrade() else {
+                    break;
+                };
+
+                // Failed refreshes keep the previous value until it expires.
+                let _ = http.refresh_cached_routes().await;
+            }
+        })
+    }
+}
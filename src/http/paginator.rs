@@ -3,6 +3,9 @@
 use crate::http::error::HttpResult;
 use sms_types::http::HttpPaginationOptions;
 
+/// Spawns a batch request future onto the runtime, set when prefetching is enabled.
+type PrefetchSpawner<T, Fut> = fn(Fut) -> tokio::task::JoinHandle<HttpResult<Vec<T>>>;
+
 /// Call a function with an update `HttpPaginationOptions` for each batch request,
 /// simplifying lazy access to large response sets such as messages etc.
 pub struct HttpPaginator<T, F, Fut> {
@@ -12,6 +15,9 @@ pub struct HttpPaginator<T, F, Fut> {
     current_index: usize,
     has_more: bool,
     initial_limit: u64,
+    total_hint: Option<u64>,
+    prefetch_spawner: Option<PrefetchSpawner<T, Fut>>,
+    prefetch: Option<tokio::task::JoinHandle<HttpResult<Vec<T>>>>,
     _phantom: std::marker::PhantomData<Fut>,
 }
 impl<T, F, Fut> HttpPaginator<T, F, Fut>
@@ -52,6 +58,9 @@ where
             current_index: 0,
             has_more: true,
             initial_limit,
+            total_hint: None,
+            prefetch_spawner: None,
+            prefetch: None,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -85,9 +94,17 @@ where
         )
     }
 
-    /// Fetch the next batch of items from the API.
+    /// Fetch the next batch of items from the API, using the prefetched batch if there is one.
     async fn fetch_next_batch(&mut self) -> HttpResult<bool> {
-        let response = (self.http_fn)(Some(self.pagination)).await?;
+        let response = match self.prefetch.take() {
+            Some(handle) => match handle.await {
+                Ok(response) => response?,
+
+                // If the prefetch task didn't complete, fetch the same page inline instead.
+                Err(_) => (self.http_fn)(Some(self.pagination)).await?,
+            },
+            None => (self.http_fn)(Some(self.pagination)).await?,
+        };
 
         let received_count = response.len() as u64;
         self.has_more = received_count >= self.initial_limit;
@@ -109,10 +126,23 @@ where
             self.pagination.offset = Some(received_count);
         }
 
+        // A known total avoids requesting an empty page after a final full page.
+        if let Some(total) = self.total_hint {
+            self.has_more = self.has_more && self.pagination.offset.unwrap_or(0) < total;
+        }
+
+        // Start fetching the following page while this one is consumed.
+        if self.has_more
+            && let Some(spawner) = self.prefetch_spawner
+        {
+            self.prefetch = Some(spawner((self.http_fn)(Some(self.pagination))));
+        }
+
         Ok(true)
     }
 
     /// Get the next item, automatically fetching next pages as needed.
+    /// Request errors end iteration, use `try_next` to receive them instead.
     ///
     /// # Example
     /// ```
@@ -131,25 +161,118 @@ where
     /// }
     /// ```
     pub async fn next(&mut self) -> Option<T> {
+        self.try_next().await.ok().flatten()
+    }
+
+    /// Get the next item, automatically fetching next pages as needed.
+    /// Returns None once there are no more items, or the request error if a fetch failed.
+    pub async fn try_next(&mut self) -> HttpResult<Option<T>> {
         if self.current_index >= self.current_batch.len() {
             // If there aren't any-more, then there is nothing to fetch next.
             if !self.has_more {
-                return None;
+                return Ok(None);
             }
 
-            match self.fetch_next_batch().await {
-                Ok(true) => {}                     // Successfully fetched more data
-                Ok(false) | Err(_) => return None, // No more data or error
+            if !self.fetch_next_batch().await? {
+                return Ok(None);
             }
         }
 
         // Return the next item if available.
         if self.current_index < self.current_batch.len() {
             let item = self.current_batch.remove(0);
-            Some(item)
+            Ok(Some(item))
         } else {
-            None
+            Ok(None)
+        }
+    }
+
+    /// Convert the paginator into a `Stream` of items, allowing `StreamExt` combinators
+    /// to be used. A request error is yielded as the final item.
+    ///
+    /// # Example
+    /// ```
+    /// use futures_util::{StreamExt, TryStreamExt};
+    /// use sms_client::http::HttpClient;
+    /// use sms_client::http::paginator::HttpPaginator;
+    ///
+    /// /// Find the first 10 messages containing "hello".
+    /// async fn find_hello(phone_number: &str, http: HttpClient) {
+    ///     let messages: Vec<_> = HttpPaginator::with_defaults(|pagination| {
+    ///         http.get_messages(phone_number, pagination)
+    ///     })
+    ///     .into_stream()
+    ///     .try_filter(|message| std::future::ready(message.message_content.contains("hello")))
+    ///     .take(10)
+    ///     .collect()
+    ///     .await;
+    /// }
+    /// ```
+    pub fn into_stream(self) -> impl futures_util::Stream<Item = HttpResult<T>> {
+        futures_util::stream::unfold(Some(self), |paginator| async move {
+            let mut paginator = paginator?;
+            match paginator.try_next().await {
+                Ok(Some(item)) => Some((Ok(item), Some(paginator))),
+                Ok(None) => None,
+
+                // Yield the error, then end the stream on the next poll.
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
+    /// Get the next page of items, or the remainder of the current page if it has been
+    /// partially consumed. Request errors end iteration, use `try_next_page` to receive them.
+    pub async fn next_page(&mut self) -> Option<Vec<T>> {
+        self.try_next_page().await.ok().flatten()
+    }
+
+    /// Get the next page of items, or the remainder of the current page if it has been
+    /// partially consumed. Returns None once there are no more pages, or the request error.
+    pub async fn try_next_page(&mut self) -> HttpResult<Option<Vec<T>>> {
+        if self.current_index >= self.current_batch.len()
+            && (!self.has_more || !self.fetch_next_batch().await?)
+        {
+            return Ok(None);
         }
+
+        self.current_index = 0;
+        Ok(Some(std::mem::take(&mut self.current_batch)))
+    }
+
+    /// Convert the paginator into a `Stream` of pages, for bulk processing a page at a time.
+    /// A request error is yielded as the final item.
+    ///
+    /// # Example
+    /// ```
+    /// use futures_util::TryStreamExt;
+    /// use sms_client::http::HttpClient;
+    /// use sms_client::http::paginator::HttpPaginator;
+    ///
+    /// /// Archive every message with a phone number, one page at a time.
+    /// async fn archive(phone_number: &str, http: HttpClient) -> sms_client::http::error::HttpResult<()> {
+    ///     let mut pages = std::pin::pin!(HttpPaginator::with_defaults(|pagination| {
+    ///         http.get_messages(phone_number, pagination)
+    ///     })
+    ///     .pages());
+    ///
+    ///     while let Some(page) = pages.try_next().await? {
+    ///         println!("Archiving {} messages", page.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn pages(self) -> impl futures_util::Stream<Item = HttpResult<Vec<T>>> {
+        futures_util::stream::unfold(Some(self), |paginator| async move {
+            let mut paginator = paginator?;
+            match paginator.try_next_page().await {
+                Ok(Some(page)) => Some((Ok(page), Some(paginator))),
+                Ok(None) => None,
+
+                // Yield the error, then end the stream on the next poll.
+                Err(e) => Some((Err(e), None)),
+            }
+        })
     }
 
     /// Collect all remaining items into a Vec.
@@ -244,7 +367,314 @@ where
     }
 
     /// Check if there are potentially more items to fetch.
+    /// Without a total hint this is a heuristic, as a final full page can't be told apart
+    /// from one with more pages after it until the next (empty) page is requested.
     pub fn has_more(&self) -> bool {
         self.has_more || self.current_index < self.current_batch.len()
     }
+
+    /// Set the total number of items in the result set, if it's known (eg: from a count
+    /// request). Pagination then stops once the offset reaches the total, without
+    /// requesting a final empty page.
+    #[must_use]
+    pub fn with_total_hint(mut self, total: u64) -> Self {
+        self.total_hint = Some(total);
+        if self.pagination.offset.unwrap_or(0) >= total {
+            self.has_more = false;
+        }
+        self
+    }
+
+    /// Get the total number of items in the result set, if it was set with `with_total_hint`.
+    pub fn total_hint(&self) -> Option<u64> {
+        self.total_hint
+    }
+
+    /// Get the number of items left to fetch or consume, if the total is known.
+    pub fn remaining_hint(&self) -> Option<u64> {
+        let buffered = self.current_batch.len().saturating_sub(self.current_index) as u64;
+        self.total_hint
+            .map(|total| total.saturating_sub(self.pagination.offset.unwrap_or(0)) + buffered)
+    }
+
+    /// Transform each item as it is fetched.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::http::HttpClient;
+    /// use sms_client::http::paginator::HttpPaginator;
+    ///
+    /// /// Collect the content of every message with a phone number.
+    /// async fn message_contents(phone_number: &str, http: HttpClient) -> Vec<String> {
+    ///     let mut contents = HttpPaginator::with_defaults(|pagination| {
+    ///         http.get_messages(phone_number, pagination)
+    ///     })
+    ///     .map(|message| message.message_content);
+    ///
+    ///     let mut all = Vec::new();
+    ///     while let Some(content) = contents.next().await {
+    ///         all.push(content);
+    ///     }
+    ///     all
+    /// }
+    /// ```
+    #[must_use]
+    pub fn map<U, M>(
+        self,
+        mut map_fn: M,
+    ) -> MappedPaginator<T, F, Fut, impl FnMut(T) -> HttpResult<Option<U>>>
+    where
+        M: FnMut(T) -> U,
+    {
+        self.try_filter_map(move |item| Ok(Some(map_fn(item))))
+    }
+
+    /// Only yield the items matching `predicate`, skipping the rest as they are fetched.
+    #[must_use]
+    pub fn filter<P>(
+        self,
+        mut predicate: P,
+    ) -> MappedPaginator<T, F, Fut, impl FnMut(T) -> HttpResult<Option<T>>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.try_filter_map(move |item| Ok(predicate(&item).then_some(item)))
+    }
+
+    /// Transform each item as it is fetched, skipping items mapped to None.
+    /// An error from `filter_map_fn` ends iteration, in the same way as a request error.
+    #[must_use]
+    pub fn try_filter_map<U, M>(self, filter_map_fn: M) -> MappedPaginator<T, F, Fut, M>
+    where
+        M: FnMut(T) -> HttpResult<Option<U>>,
+    {
+        MappedPaginator {
+            inner: self,
+            filter_map_fn,
+        }
+    }
+}
+impl<T, F, Fut> HttpPaginator<T, F, Fut>
+where
+    T: Send + 'static,
+    F: Fn(Option<HttpPaginationOptions>) -> Fut,
+    Fut: Future<Output = HttpResult<Vec<T>>> + Send + 'static,
+{
+    /// Fetch the next page in a background task while the current page is being consumed,
+    /// hiding the request round-trip when iterating large result sets. This requires the
+    /// batch futures to be `'static`, so the HTTP client must be moved into them (eg: an `Arc`).
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use sms_client::http::HttpClient;
+    /// use sms_client::http::paginator::HttpPaginator;
+    ///
+    /// async fn read_all_messages(phone_number: String, http: Arc<HttpClient>) {
+    ///     let mut paginator = HttpPaginator::with_defaults(move |pagination| {
+    ///         let http = http.clone();
+    ///         let phone_number = phone_number.clone();
+    ///         async move { http.get_messages(&phone_number, pagination).await }
+    ///     })
+    ///     .with_prefetch();
+    ///
+    ///     while let Some(message) = paginator.next().await {
+    ///         log::info!("{:?}", message);
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_prefetch(mut self) -> Self {
+        self.prefetch_spawner = Some(tokio::spawn);
+        self
+    }
+}
+/// A paginator that transforms or filters items as they are fetched,
+/// created by `HttpPaginator::map`, `filter` and `try_filter_map`.
+pub struct MappedPaginator<T, F, Fut, M> {
+    inner: HttpPaginator<T, F, Fut>,
+    filter_map_fn: M,
+}
+impl<T, U, F, Fut, M> MappedPaginator<T, F, Fut, M>
+where
+    F: Fn(Option<HttpPaginationOptions>) -> Fut,
+    Fut: Future<Output = HttpResult<Vec<T>>>,
+    M: FnMut(T) -> HttpResult<Option<U>>,
+{
+    /// Get the next item, automatically fetching next pages as needed.
+    /// Errors end iteration, use `try_next` to receive them instead.
+    pub async fn next(&mut self) -> Option<U> {
+        self.try_next().await.ok().flatten()
+    }
+
+    /// Get the next item, automatically fetching next pages as needed.
+    /// Returns None once there are no more items, or the error if a fetch or transform failed.
+    pub async fn try_next(&mut self) -> HttpResult<Option<U>> {
+        while let Some(item) = self.inner.try_next().await? {
+            if let Some(item) = (self.filter_map_fn)(item)? {
+                return Ok(Some(item));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Transform each item as it is fetched.
+    #[must_use]
+    pub fn map<V, N>(
+        self,
+        mut map_fn: N,
+    ) -> MappedPaginator<T, F, Fut, impl FnMut(T) -> HttpResult<Option<V>>>
+    where
+        N: FnMut(U) -> V,
+    {
+        self.try_filter_map(move |item| Ok(Some(map_fn(item))))
+    }
+
+    /// Only yield the items matching `predicate`, skipping the rest as they are fetched.
+    #[must_use]
+    pub fn filter<P>(
+        self,
+        mut predicate: P,
+    ) -> MappedPaginator<T, F, Fut, impl FnMut(T) -> HttpResult<Option<U>>>
+    where
+        P: FnMut(&U) -> bool,
+    {
+        self.try_filter_map(move |item| Ok(predicate(&item).then_some(item)))
+    }
+
+    /// Transform each item as it is fetched, skipping items mapped to None.
+    #[must_use]
+    pub fn try_filter_map<V, N>(
+        self,
+        mut filter_map_fn: N,
+    ) -> MappedPaginator<T, F, Fut, impl FnMut(T) -> HttpResult<Option<V>>>
+    where
+        N: FnMut(U) -> HttpResult<Option<V>>,
+    {
+        let mut inner_fn = self.filter_map_fn;
+        MappedPaginator {
+            inner: self.inner,
+            filter_map_fn: move |item: T| match inner_fn(item)? {
+                Some(item) => filter_map_fn(item),
+                None => Ok(None),
+            },
+        }
+    }
+
+    /// Convert the paginator into a `Stream` of items. An error is yielded as the final item.
+    pub fn into_stream(self) -> impl futures_util::Stream<Item = HttpResult<U>> {
+        futures_util::stream::unfold(Some(self), |paginator| async move {
+            let mut paginator = paginator?;
+            match paginator.try_next().await {
+                Ok(Some(item)) => Some((Ok(item), Some(paginator))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
+    /// Collect all remaining items into a Vec.
+    pub async fn collect_all(mut self) -> HttpResult<Vec<U>> {
+        let mut all_items = Vec::new();
+        while let Some(item) = self.try_next().await? {
+            all_items.push(item);
+        }
+        Ok(all_items)
+    }
+
+    /// Get the current pagination options state.
+    pub fn current_pagination(&self) -> &HttpPaginationOptions {
+        self.inner.current_pagination()
+    }
+
+    /// Check if there are potentially more items to fetch.
+    pub fn has_more(&self) -> bool {
+        self.inner.has_more()
+    }
+}
+
+impl<T, F, Fut> Drop for HttpPaginator<T, F, Fut> {
+    fn drop(&mut self) {
+        if let Some(handle) = self.prefetch.take() {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A result set of `0..total`, counting each page request.
+    fn source(
+        total: u64,
+        calls: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    ) -> impl Fn(Option<HttpPaginationOptions>) -> std::future::Ready<HttpResult<Vec<u64>>> {
+        move |pagination| {
+            calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let pagination = pagination.unwrap_or_default();
+            let offset = pagination.offset.unwrap_or(0);
+            let end = (offset + pagination.limit.unwrap_or(50)).min(total);
+            std::future::ready(Ok((offset.min(end)..end).collect()))
+        }
+    }
+
+    fn options(limit: u64) -> HttpPaginationOptions {
+        HttpPaginationOptions::default()
+            .with_limit(limit)
+            .with_offset(0)
+    }
+
+    #[tokio::test]
+    async fn requests_an_empty_page_without_a_total() {
+        let calls = std::sync::Arc::default();
+        let items = HttpPaginator::new(source(4, std::sync::Arc::clone(&calls)), options(2))
+            .collect_all()
+            .await
+            .unwrap();
+
+        assert_eq!(items, vec![0, 1, 2, 3]);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn total_hint_stops_after_the_final_full_page() {
+        let calls = std::sync::Arc::default();
+        let items = HttpPaginator::new(source(4, std::sync::Arc::clone(&calls)), options(2))
+            .with_total_hint(4)
+            .collect_all()
+            .await
+            .unwrap();
+
+        assert_eq!(items, vec![0, 1, 2, 3]);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn total_hint_at_the_offset_makes_no_requests() {
+        let calls = std::sync::Arc::default();
+        let paginator = HttpPaginator::new(
+            source(4, std::sync::Arc::clone(&calls)),
+            HttpPaginationOptions::default()
+                .with_limit(2)
+                .with_offset(4),
+        )
+        .with_total_hint(4);
+
+        assert!(!paginator.has_more());
+        assert!(paginator.collect_all().await.unwrap().is_empty());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn short_page_ends_pagination() {
+        let calls = std::sync::Arc::default();
+        let items = HttpPaginator::new(source(3, std::sync::Arc::clone(&calls)), options(2))
+            .collect_all()
+            .await
+            .unwrap();
+
+        assert_eq!(items, vec![0, 1, 2]);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 2);
+    }
 }
@@ -0,0 +1,136 @@
+//! Scheduled polling of modem endpoints, with intervals that adapt to modem health
+//! and how quickly values are changing. Results feed the last-known state cache.
+
+use crate::http::HttpClient;
+use crate::http::error::HttpResult;
+
+/// A modem endpoint that can be polled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PollTarget {
+    /// `HttpClient::get_network_status`
+    NetworkStatus,
+
+    /// `HttpClient::get_signal_strength`
+    SignalStrength,
+
+    /// `HttpClient::get_network_operator`
+    NetworkOperator,
+
+    /// `HttpClient::get_battery_level`
+    BatteryLevel,
+
+    /// `HttpClient::get_gnss_status`
+    GnssStatus,
+
+    /// `HttpClient::get_gnss_location`
+    GnssLocation,
+}
+impl PollTarget {
+    /// Request the target, returning a representation used to detect changes.
+    async fn poll(self, http: &HttpClient) -> HttpResult<String> {
+        Ok(match self {
+            Self::NetworkStatus => format!("{:?}", http.get_network_status().await?),
+            Self::SignalStrength => format!("{:?}", http.get_signal_strength().await?),
+            Self::NetworkOperator => format!("{:?}", http.get_network_operator().await?),
+            Self::BatteryLevel => format!("{:?}", http.get_battery_level().await?),
+            Self::GnssStatus => format!("{:?}", http.get_gnss_status().await?),
+            Self::GnssLocation => format!("{:?}", http.get_gnss_location().await?),
+        })
+    }
+}
+
+/// Polls each target in its own task. After a failed request the interval doubles up to
+/// the maximum, after a changed value it halves down to the minimum, and otherwise it
+/// returns towards the target's base interval.
+#[derive(Debug)]
+pub struct PollScheduler {
+    http: std::sync::Arc<HttpClient>,
+    targets: Vec<(PollTarget, std::time::Duration)>,
+    min_divisor: u32,
+    max_multiplier: u32,
+}
+impl PollScheduler {
+    /// The default factor the base interval is divided by for the fastest polling.
+    pub const DEFAULT_MIN_DIVISOR: u32 = 4;
+
+    /// The default factor the base interval is multiplied by for the slowest backoff.
+    pub const DEFAULT_MAX_MULTIPLIER: u32 = 8;
+
+    /// Create a scheduler without any targets.
+    #[must_use]
+    pub fn new(http: std::sync::Arc<HttpClient>) -> Self {
+        Self {
+            http,
+            targets: Vec::new(),
+            min_divisor: Self::DEFAULT_MIN_DIVISOR,
+            max_multiplier: Self::DEFAULT_MAX_MULTIPLIER,
+        }
+    }
+
+    /// Poll a target with a base interval.
+    #[must_use]
+    pub fn with_target(mut self, target: PollTarget, interval: std::time::Duration) -> Self {
+        self.targets.push((target, interval));
+        self
+    }
+
+    /// Set how far intervals can adapt: down to `base / min_divisor` when values are
+    /// changing, and up to `base * max_multiplier` when requests are failing.
+    #[must_use]
+    pub fn with_adaptive_range(mut self, min_divisor: u32, max_multiplier: u32) -> Self {
+        self.min_divisor = min_divisor.max(1);
+        self.max_multiplier = max_multiplier.max(1);
+        self
+    }
+
+    /// Spawn a polling task for each target. Abort the returned handles to stop polling.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use sms_client::http::HttpClient;
+    /// use sms_client::http::poll::{PollScheduler, PollTarget};
+    ///
+    /// fn start_polling(http: Arc<HttpClient>) {
+    ///     PollScheduler::new(http.clone())
+    ///         .with_target(PollTarget::SignalStrength, Duration::from_secs(30))
+    ///         .with_target(PollTarget::BatteryLevel, Duration::from_secs(300))
+    ///         .spawn();
+    ///
+    ///     // Later, read the polled values without making requests.
+    ///     let state = http.state_cache().snapshot();
+    /// }
+    /// ```
+    #[must_use = "dropping the handles does not stop polling, abort them instead"]
+    pub fn spawn(self) -> Vec<tokio::task::JoinHandle<()>> {
+        self.targets
+            .into_iter()
+            .map(|(target, base)| {
+                let http = self.http.clone();
+                let min = base / self.min_divisor;
+                let max = base.saturating_mul(self.max_multiplier);
+
+                tokio::spawn(async move {
+                    let mut interval = base;
+                    let mut last_value = None;
+                    loop {
+                        interval = match target.poll(&http).await {
+                            Ok(value) if last_value.as_ref().is_some_and(|last| *last != value) => {
+                                last_value = Some(value);
+                                (interval / 2).max(min)
+                            }
+                            Ok(value) => {
+                                last_value = Some(value);
+                                interval.saturating_mul(2).min(base)
+                            }
+                            Err(_) => interval.saturating_mul(2).clamp(base, max),
+                        };
+
+                        tokio::time::sleep(interval).await;
+                    }
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,163 @@
+//! Client-side token-bucket rate limiting, and server `Retry-After` delays,
+//! shared by all `HttpClient` requests.
+
+struct BucketState {
+    tokens: f64,
+    last_refill: tokio::time::Instant,
+}
+
+/// A token bucket that refills at a constant rate up to its burst size.
+/// Callers reserve a token up front, so waiting callers are served in order.
+pub(crate) struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    state: tokio::sync::Mutex<BucketState>,
+}
+impl RateLimiter {
+    /// Create a full bucket. Zero values are treated as one.
+    pub(crate) fn new(config: crate::config::HttpRateLimit) -> Self {
+        let burst = f64::from(config.burst.max(1));
+        Self {
+            rate: f64::from(config.requests_per_second.max(1)),
+            burst,
+            state: tokio::sync::Mutex::new(BucketState {
+                tokens: burst,
+                last_refill: tokio::time::Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until a request is allowed to be sent.
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut state = self.state.lock().await;
+            let now = tokio::time::Instant::now();
+            let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+
+            state.tokens = (state.tokens + elapsed * self.rate).min(self.burst);
+            state.last_refill = now;
+            state.tokens -= 1.0;
+
+            if state.tokens >= 0.0 {
+                return;
+            }
+            std::time::Duration::from_secs_f64(-state.tokens / self.rate)
+        };
+
+        tokio::time::sleep(wait).await;
+    }
+}
+impl std::fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("rate", &self.rate)
+            .field("burst", &self.burst)
+            .finish()
+    }
+}
+
+/// Holds requests after the server responds with 429 and a `Retry-After` delay.
+/// This applies whether or not a client-side rate limit is configured.
+#[derive(Debug, Default)]
+pub(crate) struct RetryAfterGate {
+    paused_until: std::sync::Mutex<Option<tokio::time::Instant>>,
+}
+impl RetryAfterGate {
+    /// Wait until the server delay, if there is one, has passed.
+    pub(crate) async fn wait(&self) {
+        let until = *self.lock();
+        if let Some(until) = until {
+            tokio::time::sleep_until(until).await;
+        }
+    }
+
+    /// Hold every request for at least `duration`.
+    pub(crate) fn pause_for(&self, duration: std::time::Duration) {
+        let until = tokio::time::Instant::now() + duration;
+        let mut paused_until = self.lock();
+        if paused_until.is_none_or(|paused_until| paused_until < until) {
+            *paused_until = Some(until);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<tokio::time::Instant>> {
+        match self.paused_until.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(requests_per_second: u32, burst: u32) -> RateLimiter {
+        RateLimiter::new(crate::config::HttpRateLimit {
+            requests_per_second,
+            burst,
+        })
+    }
+
+    /// Check the elapsed time, allowing for timer rounding to the next millisecond.
+    fn assert_elapsed(start: tokio::time::Instant, expected: std::time::Duration) {
+        let elapsed = start.elapsed();
+        assert!(
+            elapsed >= expected.saturating_sub(std::time::Duration::from_millis(1))
+                && elapsed <= expected + std::time::Duration::from_millis(2),
+            "expected {expected:?}, waited {elapsed:?}"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn burst_is_allowed_immediately() {
+        let limiter = limiter(1, 3);
+        let start = tokio::time::Instant::now();
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), std::time::Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waits_for_a_refill_once_empty() {
+        let limiter = limiter(2, 1);
+        let start = tokio::time::Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_elapsed(start, std::time::Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn refills_up_to_the_burst_size() {
+        let limiter = limiter(10, 2);
+        limiter.acquire().await;
+        limiter.acquire().await;
+
+        // Idling for longer than a full refill must not bank more than the burst.
+        tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+        let start = tokio::time::Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), std::time::Duration::ZERO);
+
+        limiter.acquire().await;
+        assert_elapsed(start, std::time::Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_after_holds_requests() {
+        let gate = RetryAfterGate::default();
+        let start = tokio::time::Instant::now();
+        gate.wait().await;
+        assert_eq!(start.elapsed(), std::time::Duration::ZERO);
+
+        gate.pause_for(std::time::Duration::from_secs(5));
+
+        // A shorter delay never cuts an existing one short.
+        gate.pause_for(std::time::Duration::from_secs(1));
+        gate.wait().await;
+        assert_elapsed(start, std::time::Duration::from_secs(5));
+    }
+}
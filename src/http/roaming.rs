@@ -0,0 +1,79 @@
+//! Roaming detection, polling the carrier network status for registration changes.
+
+use crate::http::HttpClient;
+use crate::types::modem::NetworkStatusExt;
+use sms_types::http::HttpModemNetworkStatusResponse;
+
+/// The 3GPP network registration status for "registered, home network".
+pub const REGISTRATION_HOME: u8 = 1;
+
+/// The 3GPP network registration status for "registered, roaming".
+pub const REGISTRATION_ROAMING: u8 = 5;
+
+/// Check if a network status indicates the SIM is roaming.
+#[must_use]
+pub fn is_roaming(status: &HttpModemNetworkStatusResponse) -> bool {
+    status.registration_status().is_roaming()
+}
+
+/// Emitted by the `RoamingWatcher` when the roaming state changes.
+#[derive(Clone, Debug)]
+pub struct RoamingChanged {
+    /// True if the SIM is now roaming.
+    pub roaming: bool,
+
+    /// The network status that caused the change.
+    pub status: HttpModemNetworkStatusResponse,
+}
+
+/// Periodically polls the network status, calling back when the roaming state changes.
+/// The first successful poll always calls back, reporting the initial state.
+pub struct RoamingWatcher;
+impl RoamingWatcher {
+    /// Spawn the watcher task. Abort the returned handle to stop watching.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use sms_client::http::HttpClient;
+    /// use sms_client::http::roaming::RoamingWatcher;
+    ///
+    /// fn watch(http: Arc<HttpClient>) {
+    ///     RoamingWatcher::spawn(http, Duration::from_secs(60), |change| {
+    ///         if change.roaming {
+    ///             println!("Gateway SIM is roaming!");
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    pub fn spawn<F>(
+        http: std::sync::Arc<HttpClient>,
+        interval: std::time::Duration,
+        callback: F,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        F: Fn(RoamingChanged) + Send + Sync + 'static,
+    {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            let mut last_roaming = None;
+            loop {
+                interval.tick().await;
+
+                // Failed polls are skipped, the state is only known from successful ones.
+                let Ok(status) = http.get_network_status().await else {
+                    continue;
+                };
+
+                let roaming = is_roaming(&status);
+                if last_roaming != Some(roaming) {
+                    last_roaming = Some(roaming);
+                    callback(RoamingChanged { roaming, status });
+                }
+            }
+        })
+    }
+}
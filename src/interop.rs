@@ -0,0 +1,139 @@
+//! Conversions from received SMS messages into common interop formats:
+//! RFC 5322 email, a simple JSON chat schema and Matrix `m.room.message` content.
+
+use sms_types::sms::SmsMessage;
+
+/// A plain-text email representing an SMS message, rendered with `to_rfc5322`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmailMessage {
+    /// The sender mailbox, eg: `"+447700900000" <447700900000@sms.example.com>`.
+    pub from: String,
+
+    /// The recipient mailbox.
+    pub to: String,
+
+    /// The subject line.
+    pub subject: String,
+
+    /// The plain-text body.
+    pub body: String,
+}
+impl EmailMessage {
+    /// Build an email for an SMS message. The sender address is the phone number
+    /// digits at `from_domain`, so replies can be mapped back to the number.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::interop::EmailMessage;
+    /// use sms_client::types::sms::SmsMessage;
+    ///
+    /// fn forward(message: &SmsMessage) -> String {
+    ///     EmailMessage::from_sms(message, "sms.example.com", "me@example.com").to_rfc5322()
+    /// }
+    /// ```
+    #[must_use]
+    pub fn from_sms(message: &SmsMessage, from_domain: &str, to: impl Into<String>) -> Self {
+        let local_part: String = message
+            .phone_number
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect();
+
+        Self {
+            from: format!("\"{}\" <{local_part}@{from_domain}>", message.phone_number),
+            to: to.into(),
+            subject: format!("SMS from {}", message.phone_number),
+            body: message.message_content.clone(),
+        }
+    }
+
+    /// Render the email as an RFC 5322 message with CRLF line endings, dated now.
+    #[must_use]
+    pub fn to_rfc5322(&self) -> String {
+        let body = self
+            .body
+            .replace("\r\n", "\n")
+            .replace('\r', "\n")
+            .replace('\n', "\r\n");
+
+        format!(
+            "Date: {}\r\nFrom: {}\r\nTo: {}\r\nSubject: {}\r\nMIME-Version: 1.0\r\n\
+             Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{body}\r\n",
+            rfc5322_date(std::time::SystemTime::now()),
+            self.from,
+            self.to,
+            self.subject
+        )
+    }
+}
+
+/// A minimal JSON chat message schema, `{"sender": "...", "text": "..."}`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ChatMessage {
+    /// The sender's phone number.
+    pub sender: String,
+
+    /// The message text.
+    pub text: String,
+}
+impl From<&SmsMessage> for ChatMessage {
+    fn from(message: &SmsMessage) -> Self {
+        Self {
+            sender: message.phone_number.clone(),
+            text: message.message_content.clone(),
+        }
+    }
+}
+
+/// Matrix `m.room.message` event content for a plain-text message.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MatrixMessageContent {
+    /// The message type, always `m.text`.
+    pub msgtype: String,
+
+    /// The message body, prefixed with the sender's phone number.
+    pub body: String,
+}
+impl From<&SmsMessage> for MatrixMessageContent {
+    fn from(message: &SmsMessage) -> Self {
+        Self {
+            msgtype: "m.text".to_string(),
+            body: format!("{}: {}", message.phone_number, message.message_content),
+        }
+    }
+}
+
+/// Format a time as an RFC 5322 date in UTC, eg: `Fri, 16 Oct 2026 10:42:58 +0000`.
+fn rfc5322_date(time: std::time::SystemTime) -> String {
+    const DAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let secs = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let days = secs / 86_400;
+    let secs_of_day = secs % 86_400;
+
+    // Civil date from days since the epoch (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{}, {day:02} {} {year} {:02}:{:02}:{:02} +0000",
+        DAYS[usize::try_from(days % 7).unwrap_or_default()],
+        MONTHS[usize::try_from(month - 1).unwrap_or_default()],
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
+}
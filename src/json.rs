@@ -0,0 +1,19 @@
+//! JSON parsing for WebSocket frames and HTTP envelopes, using simd-json when enabled.
+
+/// Parse a value from JSON bytes. With the `simd-json` feature the input is copied into
+/// a mutable buffer for simd-json. If that fails the input is parsed again by `serde_json`
+/// to build the error, so its classification and position match the default parser.
+pub(crate) fn from_slice<T>(bytes: &[u8]) -> serde_json::Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    #[cfg(feature = "simd-json")]
+    {
+        let mut buffer = bytes.to_vec();
+        if let Ok(value) = simd_json::serde::from_slice(&mut buffer) {
+            return Ok(value);
+        }
+    }
+
+    serde_json::from_slice(bytes)
+}
@@ -7,10 +7,23 @@
 #![allow(clippy::missing_errors_doc)]
 
 use crate::error::{ClientError, ClientResult};
-pub use sms_types as types;
 
 pub mod config;
+pub mod counters;
+pub mod dump;
 pub mod error;
+pub mod global;
+pub mod prelude;
+pub mod report;
+pub mod state;
+pub mod templates;
+pub mod types;
+
+#[cfg(any(feature = "http", feature = "websocket"))]
+mod json;
+
+#[cfg(any(feature = "http-tls-rustls", feature = "websocket-tls-rustls"))]
+mod tls;
 
 #[cfg(feature = "http")]
 pub mod http;
@@ -18,6 +31,24 @@ pub mod http;
 #[cfg(feature = "websocket")]
 pub mod ws;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+#[cfg(feature = "mock")]
+pub mod mock;
+
+#[cfg(feature = "interop")]
+pub mod interop;
+
+#[cfg(feature = "discovery")]
+pub mod discovery;
+
+#[cfg(feature = "webhook")]
+pub mod webhook;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 /// SMS Client.
 #[derive(Clone, Debug)]
 pub struct Client {
@@ -26,27 +57,82 @@ pub struct Client {
 
     #[cfg(feature = "websocket")]
     ws_client: Option<std::sync::Arc<tokio::sync::Mutex<ws::WebSocketClient>>>,
+
+    state: state::StateCache,
+    counters: counters::Counters,
+    components: report::ConfiguredComponents,
 }
 impl Client {
+    /// The interval between delivery report requests in `send_and_confirm`.
+    #[cfg(feature = "http")]
+    pub const DELIVERY_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
     /// Create an SMS client with a connection config.
     pub fn new(config: config::ClientConfig) -> ClientResult<Self> {
+        let components = report::ConfiguredComponents::from_config(&config);
         let tls = config.tls;
+        let debug_dump = config.debug_dump.map(dump::DebugDump::open).transpose()?;
 
         #[cfg(feature = "http")]
         let http_client = if let Some(http_config) = config.http {
-            Some(std::sync::Arc::new(http::HttpClient::new(
-                http_config,
-                tls.as_ref(),
-            )?))
+            let mut http_client = http::HttpClient::new(http_config, tls.as_ref())?;
+            if let Some(dump) = &debug_dump {
+                http_client.set_debug_dump(dump.clone());
+            }
+
+            let http_client = std::sync::Arc::new(http_client);
+            spawn_sim_unlock(&http_client);
+            spawn_modem_cache_refresher(&http_client);
+            Some(http_client)
         } else {
             None
         };
 
+        // Share the HTTP client's state cache so both paths update the same state.
+        #[cfg(feature = "http")]
+        let state = http_client
+            .as_ref()
+            .map(|http| http.state_cache().clone())
+            .unwrap_or_default();
+
+        #[cfg(not(feature = "http"))]
+        let state = state::StateCache::default();
+
+        // Share the HTTP client's counters, the same as the state cache.
+        #[cfg(feature = "http")]
+        let counters = http_client
+            .as_ref()
+            .map(|http| http.counters().clone())
+            .unwrap_or_default();
+
+        #[cfg(not(feature = "http"))]
+        let counters = counters::Counters::default();
+
         #[cfg(feature = "websocket")]
         let ws_client = config.websocket.map(|ws_config| {
-            std::sync::Arc::new(tokio::sync::Mutex::new(ws::WebSocketClient::new(
-                ws_config, tls,
-            )))
+            let mut ws_client = ws::WebSocketClient::new(ws_config, tls);
+            ws_client.set_state_cache(state.clone());
+            ws_client.set_counters(counters.clone());
+            if let Some(dump) = &debug_dump {
+                ws_client.set_debug_dump(dump.clone());
+            }
+
+            // The SIM may be locked again after the modem restarts, so a cached lock state is
+            // discarded on status updates, and then checked again if a PIN is configured.
+            #[cfg(feature = "http")]
+            if let Some(http) = &http_client {
+                let http = std::sync::Arc::clone(http);
+                ws_client
+                    .subscribe(move |event| {
+                        if matches!(event, sms_types::events::Event::ModemStatusUpdate { .. }) {
+                            http.forget_pin_status();
+                            spawn_sim_unlock(&http);
+                        }
+                    })
+                    .detach();
+            }
+
+            std::sync::Arc::new(tokio::sync::Mutex::new(ws_client))
         });
 
         Ok(Self {
@@ -55,9 +141,219 @@ impl Client {
 
             #[cfg(feature = "websocket")]
             ws_client,
+
+            state,
+            counters,
+            components,
         })
     }
 
+    /// Get cumulative counts of sent and received messages, delivery failures, reconnects and
+    /// HTTP errors since the client was created.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    ///
+    /// fn stats_json(client: &Client) -> String {
+    ///     serde_json::to_string(&client.counters()).unwrap_or_default()
+    /// }
+    /// ```
+    #[must_use]
+    pub fn counters(&self) -> counters::CounterSnapshot {
+        self.counters.snapshot()
+    }
+
+    /// Browse the local network for gateways advertised with mDNS, for `timeout`.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use sms_client::Client;
+    /// use sms_client::error::ClientResult;
+    ///
+    /// async fn connect_to_first_gateway() -> ClientResult<Option<Client>> {
+    ///     let gateways = Client::discover(Duration::from_secs(3)).await?;
+    ///     let Some(gateway) = gateways.first() else {
+    ///         return Ok(None);
+    ///     };
+    ///
+    ///     Client::new(gateway.to_config().with_auth("token123")).map(Some)
+    /// }
+    /// ```
+    #[cfg(feature = "discovery")]
+    pub async fn discover(
+        timeout: std::time::Duration,
+    ) -> ClientResult<Vec<discovery::DiscoveredGateway>> {
+        discovery::browse(timeout).await
+    }
+
+    /// Report the compiled features, configured components and server version.
+    /// The server version is requested over HTTP, and is None if that fails.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    ///
+    /// async fn log_startup(client: &Client) {
+    ///     let report = client.feature_report().await;
+    ///     log::info!("{}", serde_json::to_string(&report).unwrap());
+    /// }
+    /// ```
+    #[cfg_attr(not(feature = "http"), allow(clippy::unused_async))]
+    pub async fn feature_report(&self) -> report::FeatureReport {
+        #[cfg(feature = "http")]
+        let server_version = match &self.http_client {
+            Some(http) => http.get_version().await.ok(),
+            None => None,
+        };
+
+        #[cfg(not(feature = "http"))]
+        let server_version = None;
+
+        report::FeatureReport {
+            client_version: env!("CARGO_PKG_VERSION"),
+            compiled: report::CompiledFeatures::current(),
+            configured: self.components,
+            server_version,
+        }
+    }
+
+    /// Check HTTP reachability, authorization, WebSocket connectivity and whether the modem
+    /// is registered to a network, in one call. Failed checks are reported, not returned as errors.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    ///
+    /// async fn ready(client: &Client) -> bool {
+    ///     let report = client.health_check().await;
+    ///     if !report.is_healthy() {
+    ///         eprintln!("Not ready: {report:?}");
+    ///     }
+    ///     report.is_healthy()
+    /// }
+    /// ```
+    #[cfg_attr(
+        not(any(feature = "http", feature = "websocket")),
+        allow(clippy::unused_async)
+    )]
+    pub async fn health_check(&self) -> report::HealthReport {
+        use report::HealthCheck;
+        #[cfg(feature = "http")]
+        use types::modem::NetworkStatusExt;
+
+        #[cfg(feature = "http")]
+        let (http_reachable, auth_valid, modem_online) = match &self.http_client {
+            Some(http) => {
+                let http_reachable = match http.get_version().await {
+                    Ok(_) => HealthCheck::Healthy,
+                    Err(e) => HealthCheck::Unhealthy(e.to_string()),
+                };
+
+                // The version route doesn't need authorization, so check with a minimal query.
+                let pagination = types::http::HttpPaginationOptions::default().with_limit(1);
+                let auth_valid = match http.get_latest_numbers(Some(pagination)).await {
+                    Ok(_) => HealthCheck::Healthy,
+                    Err(e) => HealthCheck::Unhealthy(e.to_string()),
+                };
+
+                let modem_online = match http.get_network_status().await {
+                    Ok(status) if status.registration_status().is_registered() => {
+                        HealthCheck::Healthy
+                    }
+                    Ok(status) => HealthCheck::Unhealthy(format!(
+                        "Modem is not registered to a network ({:?})",
+                        status.registration_status()
+                    )),
+                    Err(e) => HealthCheck::Unhealthy(e.to_string()),
+                };
+
+                (http_reachable, auth_valid, modem_online)
+            }
+            None => (
+                HealthCheck::NotConfigured,
+                HealthCheck::NotConfigured,
+                HealthCheck::NotConfigured,
+            ),
+        };
+
+        #[cfg(not(feature = "http"))]
+        let (http_reachable, auth_valid, modem_online) = (
+            HealthCheck::NotConfigured,
+            HealthCheck::NotConfigured,
+            HealthCheck::NotConfigured,
+        );
+
+        #[cfg(feature = "websocket")]
+        let websocket_connected = if self.ws_client.is_none() {
+            HealthCheck::NotConfigured
+        } else if self.is_websocket_connected().await {
+            HealthCheck::Healthy
+        } else {
+            HealthCheck::Unhealthy("WebSocket is not connected".to_string())
+        };
+
+        #[cfg(not(feature = "websocket"))]
+        let websocket_connected = HealthCheck::NotConfigured;
+
+        report::HealthReport {
+            http_reachable,
+            auth_valid,
+            websocket_connected,
+            modem_online,
+        }
+    }
+
+    /// Get the last-known modem state, without making any requests.
+    /// This is updated by successful HTTP modem requests, WebSocket connection changes,
+    /// and the modem status updates and GNSS position reports received as WebSocket events.
+    #[must_use]
+    pub fn last_known_state(&self) -> state::LastKnownState {
+        self.state.snapshot()
+    }
+
+    /// Write a JSON support bundle to `path`, containing the feature report, the
+    /// HTTP URL (without credentials), the last-known modem state, the counters
+    /// and the most recent connection and parse errors.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    ///
+    /// async fn on_failure(client: &Client) {
+    ///     if let Err(e) = client.generate_support_bundle("sms-client-support.json").await {
+    ///         log::error!("Failed to write support bundle: {e}");
+    ///     }
+    /// }
+    /// ```
+    pub async fn generate_support_bundle(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> ClientResult<()> {
+        #[cfg(feature = "http")]
+        let http_url = self
+            .http_client
+            .as_ref()
+            .map(|http| report::redact_url(http.base_url()));
+
+        #[cfg(not(feature = "http"))]
+        let http_url = None;
+
+        let bundle = report::SupportBundle {
+            generated_at_ms: dump::now_ms(),
+            features: self.feature_report().await,
+            http_url,
+            last_known_state: self.state.snapshot(),
+            counters: self.counters.snapshot(),
+            recent_errors: self.counters.recent_errors(),
+        };
+
+        let json = serde_json::to_vec_pretty(&bundle).map_err(std::io::Error::from)?;
+        tokio::fs::write(path, json).await?;
+        Ok(())
+    }
+
     /// Borrow the optional inner HTTP client.
     #[cfg(feature = "http")]
     pub fn http(&self) -> ClientResult<&http::HttpClient> {
@@ -75,6 +371,100 @@ impl Client {
             .ok_or(ClientError::ConfigError("HttpClient"))
     }
 
+    /// Send a message via HTTP, then wait for a final delivery report for it, correlating
+    /// the returned `message_id`. If the WebSocket is connected, delivery report events are
+    /// watched. Otherwise, delivery reports are polled every `DELIVERY_POLL_INTERVAL`.
+    /// This waits until a report has a final status, or `timeout` elapses
+    /// (`ClientError::DeliveryTimeout`).
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use sms_client::Client;
+    /// use sms_client::types::sms::SmsOutgoingMessage;
+    ///
+    /// async fn send(client: &Client) {
+    ///     let message = SmsOutgoingMessage::simple_message("+447700900000", "Hello!");
+    ///     match client.send_and_confirm(&message, Duration::from_secs(120)).await {
+    ///         Ok((_, group)) if group.is_success() => println!("Delivered!"),
+    ///         Ok((_, group)) => println!("Delivery failed: {group:?}"),
+    ///         Err(e) => println!("Error: {e}"),
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "http")]
+    pub async fn send_and_confirm(
+        &self,
+        message: &sms_types::sms::SmsOutgoingMessage,
+        timeout: std::time::Duration,
+    ) -> ClientResult<(
+        sms_types::http::HttpSmsSendResponse,
+        types::delivery::DeliveryStatusGroup,
+    )> {
+        let http = self.http()?;
+
+        // Subscribe before sending, so a fast delivery report can't be missed.
+        #[cfg(feature = "websocket")]
+        let delivery_events = self.delivery_event_receiver().await;
+
+        let response = http.send_sms(message).await?;
+        let message_id = response.message_id;
+
+        let wait_for_final = async {
+            // Delivery failures from events are counted by the WebSocket worker.
+            #[cfg(feature = "websocket")]
+            if let Some((_subscription, mut events)) = delivery_events {
+                while let Some((id, group)) = events.recv().await {
+                    if id == message_id && group.is_final() {
+                        return Ok(group);
+                    }
+                }
+            }
+
+            loop {
+                let reports = http.get_delivery_reports(message_id, None).await?;
+                let group = reports.iter().rev().find_map(|report| {
+                    let group = types::delivery::DeliveryReportExt::status_group(report);
+                    group.is_final().then_some(group)
+                });
+                if let Some(group) = group {
+                    http.counters().record_delivery(group);
+                    return Ok::<_, ClientError>(group);
+                }
+
+                tokio::time::sleep(Self::DELIVERY_POLL_INTERVAL).await;
+            }
+        };
+
+        match tokio::time::timeout(timeout, wait_for_final).await {
+            Ok(group) => Ok((response, group?)),
+            Err(_) => Err(ClientError::DeliveryTimeout(message_id)),
+        }
+    }
+
+    /// Subscribe to delivery report events as `(message_id, group)`, if the WebSocket is connected.
+    #[cfg(all(feature = "http", feature = "websocket"))]
+    async fn delivery_event_receiver(
+        &self,
+    ) -> Option<(
+        ws::SubscriptionHandle,
+        tokio::sync::mpsc::UnboundedReceiver<(i64, types::delivery::DeliveryStatusGroup)>,
+    )> {
+        let ws_guard = self.ws_client.as_ref()?.lock().await;
+        if !ws_guard.is_connected().await {
+            return None;
+        }
+
+        let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
+        let subscription = ws_guard.subscribe(move |event| {
+            if let sms_types::events::Event::DeliveryReport { message_id, report } = event {
+                let group = types::delivery::DeliveryStatusGroup::from_tp_status(report.status);
+                let _ = event_tx.send((message_id, group));
+            }
+        });
+        Some((subscription, event_rx))
+    }
+
     /// Set the callback for incoming WebSocket messages. The callback will include the WebSocket
     /// message and an Arc to the current Client allowing for easy use within the callback!
     /// This must be called before starting the WebSocket connection.
@@ -117,6 +507,327 @@ impl Client {
         Ok(())
     }
 
+    /// Set a callback for WebSocket messages that can't be decoded into an event, receiving
+    /// the raw text and the decode error. This is useful to alert on schema drift between the
+    /// server and client, instead of silently losing events. This must be called before
+    /// starting the WebSocket connection.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    ///
+    /// async fn alert_on_schema_drift(client: &Client) {
+    ///     let _ = client
+    ///         .on_parse_error(|text, error| {
+    ///             eprintln!("Undecodable event ({error}): {text}");
+    ///         })
+    ///         .await;
+    /// }
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn on_parse_error<F>(&self, callback: F) -> ClientResult<()>
+    where
+        F: Fn(&str, &serde_json::Error) + Send + Sync + 'static,
+    {
+        let ws_client = self
+            .ws_client
+            .as_ref()
+            .ok_or(ClientError::ConfigError("WebSocketClient"))?;
+
+        let mut ws_guard = ws_client.lock().await;
+        ws_guard.on_parse_error(callback);
+        Ok(())
+    }
+
+    /// Set a callback for WebSocket events with an unrecognised type, eg: new event types
+    /// sent by a newer server, so they still reach the application. It receives the event
+    /// type name and raw data. Unknown events are only delivered here, they never reach
+    /// `on_message` handlers, subscribers, streams or the event queue. Known events that fail
+    /// to decode go to the parse error callback instead. This must be called before starting
+    /// the WebSocket connection.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    ///
+    /// async fn log_unknown_events(client: &Client) {
+    ///     let _ = client
+    ///         .on_unknown_event(|event| {
+    ///             println!("Unknown event '{}': {}", event.event_type, event.data);
+    ///         })
+    ///         .await;
+    /// }
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn on_unknown_event<F>(&self, callback: F) -> ClientResult<()>
+    where
+        F: Fn(ws::UnknownEvent) + Send + Sync + 'static,
+    {
+        let ws_client = self
+            .ws_client
+            .as_ref()
+            .ok_or(ClientError::ConfigError("WebSocketClient"))?;
+
+        let mut ws_guard = ws_client.lock().await;
+        ws_guard.on_unknown_event(callback);
+        Ok(())
+    }
+
+    /// Set an async callback for incoming WebSocket messages, awaited in its own task so
+    /// handlers can make requests without spawning. At most `max_concurrent` handlers run
+    /// at once, further events wait in order. This must be called before starting the
+    /// WebSocket connection.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    /// use sms_client::types::events::Event;
+    /// use sms_client::types::sms::SmsOutgoingMessage;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client: Client = unimplemented!("See other examples");
+    ///
+    ///     client.on_message_async(8, |message, client| async move {
+    ///         if let Event::IncomingMessage(sms) = message {
+    ///             let reply = SmsOutgoingMessage::simple_message(sms.phone_number, "Thanks!");
+    ///             if let Ok(http) = client.http() {
+    ///                 let _ = http.send_sms(&reply).await;
+    ///             }
+    ///         }
+    ///     }).await?
+    /// }
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn on_message_async<F, Fut>(
+        &self,
+        max_concurrent: usize,
+        callback: F,
+    ) -> ClientResult<()>
+    where
+        F: Fn(sms_types::events::Event, std::sync::Arc<Self>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let ws_client = self
+            .ws_client
+            .as_ref()
+            .ok_or(ClientError::ConfigError("WebSocketClient"))?;
+
+        let mut ws_guard = ws_client.lock().await;
+        let client_arc = std::sync::Arc::new(self.clone());
+
+        ws_guard.on_message_async(max_concurrent, move |msg| {
+            callback(msg, std::sync::Arc::clone(&client_arc))
+        });
+
+        Ok(())
+    }
+
+    /// Subscribe an additional callback for incoming WebSocket messages. Any number of
+    /// subscribers can be added, and unlike `on_message` they never replace each other.
+    /// The callback is removed when the returned handle is dropped or cancelled.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    /// use sms_client::types::events::Event;
+    ///
+    /// async fn audit(client: &Client) {
+    ///     let handle = client.subscribe(|message| {
+    ///         log::info!("Audit: {message:?}");
+    ///     }).await.expect("Missing WebSocket configuration!");
+    ///
+    ///     // Keep receiving messages for the lifetime of the client.
+    ///     handle.detach();
+    /// }
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn subscribe<F>(&self, callback: F) -> ClientResult<ws::SubscriptionHandle>
+    where
+        F: Fn(sms_types::events::Event) + Send + Sync + 'static,
+    {
+        let ws_client = self
+            .ws_client
+            .as_ref()
+            .ok_or(ClientError::ConfigError("WebSocketClient"))?;
+
+        Ok(ws_client.lock().await.subscribe(callback))
+    }
+
+    /// Add an automation rule that runs an async action when an event condition has held
+    /// for long enough (see `AutomationRule`). The rule is removed when the returned handle
+    /// is dropped or cancelled.
+    #[cfg(feature = "websocket")]
+    pub async fn add_automation(
+        &self,
+        rule: ws::automation::AutomationRule,
+    ) -> ClientResult<ws::SubscriptionHandle> {
+        let ws_client = self
+            .ws_client
+            .as_ref()
+            .ok_or(ClientError::ConfigError("WebSocketClient"))?;
+
+        Ok(ws_client.lock().await.add_automation(rule))
+    }
+
+    /// Get a stream of incoming WebSocket messages, as a pull-based alternative to callbacks.
+    /// Messages are buffered until read, and the stream stops receiving once dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use futures_util::StreamExt;
+    /// use sms_client::Client;
+    ///
+    /// async fn read_events(client: &Client) {
+    ///     let mut stream = client.websocket_stream().await.expect("Missing WebSocket configuration!");
+    ///     client.start_background_websocket().await.expect("Failed to start WebSocket!");
+    ///
+    ///     while let Some(message) = stream.next().await {
+    ///         log::info!("{message:?}");
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn websocket_stream(&self) -> ClientResult<ws::EventStream> {
+        let ws_client = self
+            .ws_client
+            .as_ref()
+            .ok_or(ClientError::ConfigError("WebSocketClient"))?;
+
+        Ok(ws_client.lock().await.stream())
+    }
+
+    /// Subscribe a callback for incoming SMS messages only, receiving the message directly.
+    /// The callback is removed when the returned handle is dropped or cancelled.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    ///
+    /// async fn log_incoming(client: &Client) {
+    ///     client
+    ///         .on_incoming(|sms| log::info!("SMS from {}: {}", sms.phone_number, sms.message_content))
+    ///         .await
+    ///         .expect("Missing WebSocket configuration!")
+    ///         .detach();
+    /// }
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn on_incoming<F>(&self, callback: F) -> ClientResult<ws::SubscriptionHandle>
+    where
+        F: Fn(sms_types::sms::SmsMessage) + Send + Sync + 'static,
+    {
+        self.subscribe(move |event| {
+            if let sms_types::events::Event::IncomingMessage(message) = event {
+                callback(message);
+            }
+        })
+        .await
+    }
+
+    /// Subscribe a callback for outgoing SMS messages only, receiving the message directly.
+    /// The callback is removed when the returned handle is dropped or cancelled.
+    #[cfg(feature = "websocket")]
+    pub async fn on_outgoing<F>(&self, callback: F) -> ClientResult<ws::SubscriptionHandle>
+    where
+        F: Fn(sms_types::sms::SmsMessage) + Send + Sync + 'static,
+    {
+        self.subscribe(move |event| {
+            if let sms_types::events::Event::OutgoingMessage(message) = event {
+                callback(message);
+            }
+        })
+        .await
+    }
+
+    /// Subscribe a callback for delivery reports only, receiving the message ID and typed status.
+    /// The callback is removed when the returned handle is dropped or cancelled.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    ///
+    /// async fn log_failures(client: &Client) {
+    ///     client
+    ///         .on_delivery_report(|message_id, status| {
+    ///             if !status.group().is_success() {
+    ///                 log::warn!("Message {message_id} failed: {status:?}");
+    ///             }
+    ///         })
+    ///         .await
+    ///         .expect("Missing WebSocket configuration!")
+    ///         .detach();
+    /// }
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn on_delivery_report<F>(&self, callback: F) -> ClientResult<ws::SubscriptionHandle>
+    where
+        F: Fn(i64, types::delivery::DeliveryStatus) + Send + Sync + 'static,
+    {
+        self.subscribe(move |event| {
+            if let sms_types::events::Event::DeliveryReport { message_id, report } = event {
+                callback(
+                    message_id,
+                    types::delivery::DeliveryStatus::from(report.status),
+                );
+            }
+        })
+        .await
+    }
+
+    /// Subscribe a callback for modem status changes only, receiving the previous and current status.
+    /// The callback is removed when the returned handle is dropped or cancelled.
+    #[cfg(feature = "websocket")]
+    pub async fn on_modem_status<F>(&self, callback: F) -> ClientResult<ws::SubscriptionHandle>
+    where
+        F: Fn(sms_types::events::ModemStatusUpdateState, sms_types::events::ModemStatusUpdateState)
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.subscribe(move |event| {
+            if let sms_types::events::Event::ModemStatusUpdate { previous, current } = event {
+                callback(previous, current);
+            }
+        })
+        .await
+    }
+
+    /// Subscribe a callback for GNSS position reports only, receiving the report directly.
+    /// The callback is removed when the returned handle is dropped or cancelled.
+    #[cfg(feature = "websocket")]
+    pub async fn on_gnss<F>(&self, callback: F) -> ClientResult<ws::SubscriptionHandle>
+    where
+        F: Fn(sms_types::gnss::PositionReport) + Send + Sync + 'static,
+    {
+        self.subscribe(move |event| {
+            if let sms_types::events::Event::GNSSPositionReport(report) = event {
+                callback(report);
+            }
+        })
+        .await
+    }
+
+    /// Subscribe a callback for WebSocket connection changes only, receiving whether it is
+    /// now connected and whether a reconnect will be attempted.
+    /// The callback is removed when the returned handle is dropped or cancelled.
+    #[cfg(feature = "websocket")]
+    pub async fn on_connection_update<F>(&self, callback: F) -> ClientResult<ws::SubscriptionHandle>
+    where
+        F: Fn(bool, bool) + Send + Sync + 'static,
+    {
+        self.subscribe(move |event| {
+            if let sms_types::events::Event::WebsocketConnectionUpdate {
+                connected,
+                reconnect,
+            } = event
+            {
+                callback(connected, reconnect);
+            }
+        })
+        .await
+    }
+
     /// Set the callback for incoming WebSocket messages (simple version without client copy).
     /// This must be called before starting the WebSocket connection.
     ///
@@ -154,6 +865,32 @@ impl Client {
         Ok(())
     }
 
+    /// Change which events the server sends, without rebuilding the WebSocket client.
+    /// If connected, this reconnects with the new filters. None means all events are sent.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    ///
+    /// async fn only_incoming(client: &Client) {
+    ///     client.set_event_filters(Some(vec!["incoming"])).await.expect("Failed to set filters!");
+    /// }
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn set_event_filters(
+        &self,
+        events: Option<Vec<impl Into<String>>>,
+    ) -> ClientResult<()> {
+        let ws_client = self
+            .ws_client
+            .as_ref()
+            .ok_or(ClientError::ConfigError("WebSocketClient"))?;
+
+        let events = events.map(|events| events.into_iter().map(Into::into).collect());
+        ws_client.lock().await.set_event_filters(events)?;
+        Ok(())
+    }
+
     /// Start the WebSocket connection.
     #[cfg(feature = "websocket")]
     pub async fn start_background_websocket(&self) -> ClientResult<()> {
@@ -196,6 +933,62 @@ impl Client {
         Ok(())
     }
 
+    /// Stop the WebSocket connection and wait up to `grace` for queued events and running
+    /// async handlers to finish. Returns true if they all finished in time.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use sms_client::Client;
+    ///
+    /// async fn on_exit(client: &Client) {
+    ///     if !client.shutdown_websocket(Duration::from_secs(10)).await.unwrap_or(false) {
+    ///         eprintln!("Some message handlers did not finish before shutdown");
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn shutdown_websocket(&self, grace: std::time::Duration) -> ClientResult<bool> {
+        let ws_client = self
+            .ws_client
+            .as_ref()
+            .ok_or(ClientError::ConfigError("WebSocketClient"))?;
+
+        let mut ws_guard = ws_client.lock().await;
+        Ok(ws_guard.shutdown(grace).await?)
+    }
+
+    /// Get a receiver for the WebSocket connection state, so state changes can be awaited
+    /// instead of polling `is_websocket_connected`.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::Client;
+    /// use sms_client::ws::ConnectionState;
+    ///
+    /// async fn wait_until_connected(client: &Client) -> bool {
+    ///     let Ok(mut state) = client.connection_state().await else {
+    ///         return false;
+    ///     };
+    ///     state
+    ///         .wait_for(|state| *state == ConnectionState::Connected)
+    ///         .await
+    ///         .is_ok()
+    /// }
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn connection_state(
+        &self,
+    ) -> ClientResult<tokio::sync::watch::Receiver<ws::ConnectionState>> {
+        let ws_client = self
+            .ws_client
+            .as_ref()
+            .ok_or(ClientError::ConfigError("WebSocketClient"))?;
+
+        let ws_guard = ws_client.lock().await;
+        Ok(ws_guard.connection_state())
+    }
+
     /// Check if the WebSocket is currently connected.
     #[cfg(feature = "websocket")]
     pub async fn is_websocket_connected(&self) -> bool {
@@ -225,3 +1018,39 @@ impl Drop for Client {
         // This is just here to ensure proper cleanup ordering.
     }
 }
+
+/// Refresh cached modem responses in the background before they expire, if the modem cache
+/// is enabled and a Tokio runtime is running. The task stops once the HTTP client is dropped.
+#[cfg(feature = "http")]
+fn spawn_modem_cache_refresher(http: &std::sync::Arc<http::HttpClient>) {
+    let Some(ttl) = http.modem_cache_ttl().filter(|ttl| !ttl.is_zero()) else {
+        return;
+    };
+    if tokio::runtime::Handle::try_current().is_err() {
+        return;
+    }
+
+    http::modem_cache::ModemCacheRefresher::spawn(std::sync::Arc::clone(http), ttl * 4 / 5);
+}
+
+/// Supply the configured SIM PIN in the background, if there is one and a Tokio runtime is running.
+#[cfg(feature = "http")]
+fn spawn_sim_unlock(http: &std::sync::Arc<http::HttpClient>) {
+    if !http.has_sim_pin() {
+        return;
+    }
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+
+    let http = std::sync::Arc::clone(http);
+    runtime.spawn(async move {
+        if let Err(e) = http.ensure_sim_unlocked().await {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(error = %e, "Failed to unlock the SIM");
+
+            #[cfg(not(feature = "tracing"))]
+            let _ = e;
+        }
+    });
+}
@@ -0,0 +1,476 @@
+//! In-memory mock client for integration tests, without a running SMS-API.
+
+use crate::http::SmsApi;
+use crate::http::capabilities::ServerCapabilities;
+use crate::http::error::{HttpError, HttpResult};
+use crate::http::meta::{ResponseMeta, WithMeta};
+use crate::http::mms::{HttpOutgoingMmsMessage, MmsSendResult};
+use crate::types::modem::{ModemRestartResponse, SimPinStatus};
+use sms_types::events::Event;
+use sms_types::gnss::{FixStatus, PositionReport};
+use sms_types::http::{
+    HttpModemBatteryLevelResponse, HttpModemNetworkOperatorResponse,
+    HttpModemNetworkStatusResponse, HttpModemSignalStrengthResponse, HttpPaginationOptions,
+    HttpSmsDeviceInfoData, HttpSmsSendResponse, LatestNumberFriendlyNamePair,
+};
+use sms_types::sms::{SmsDeliveryReport, SmsMessage, SmsOutgoingMessage};
+
+/// A callback to be run when the mock emits a simulated WebSocket event.
+pub type MockEventCallback = std::sync::Arc<dyn Fn(Event) + Send + Sync>;
+
+/// A programmable response to a `send_sms` call.
+pub type MockSendHandler =
+    Box<dyn Fn(&SmsOutgoingMessage) -> HttpResult<HttpSmsSendResponse> + Send + Sync>;
+
+/// Programmable responses for modem and system queries. Any value left as None
+/// makes the corresponding request fail with an `HttpError::ApiError`.
+#[derive(Clone, Debug, Default)]
+pub struct MockResponses {
+    /// Response for `get_network_status`.
+    pub network_status: Option<HttpModemNetworkStatusResponse>,
+
+    /// Response for `get_signal_strength`.
+    pub signal_strength: Option<HttpModemSignalStrengthResponse>,
+
+    /// Response for `get_network_operator`.
+    pub network_operator: Option<HttpModemNetworkOperatorResponse>,
+
+    /// Response for `get_service_provider`.
+    pub service_provider: Option<String>,
+
+    /// Response for `get_battery_level`.
+    pub battery_level: Option<HttpModemBatteryLevelResponse>,
+
+    /// Response for `get_gnss_status`.
+    pub gnss_status: Option<FixStatus>,
+
+    /// Response for `get_gnss_location`.
+    pub gnss_location: Option<PositionReport>,
+
+    /// Response for `get_device_info`.
+    pub device_info: Option<HttpSmsDeviceInfoData>,
+
+    /// Response for `get_latest_numbers`, paginated by the mock.
+    pub latest_numbers: Vec<LatestNumberFriendlyNamePair>,
+
+    /// Response for `get_phone_number`.
+    pub phone_number: Option<String>,
+
+    /// Response for `get_version`, which `get_capabilities` is also parsed from.
+    pub version: Option<String>,
+
+    /// Response for `get_pin_status`.
+    pub pin_status: Option<SimPinStatus>,
+
+    /// The PIN accepted by `unlock_sim`, which then sets `pin_status` to `SimPinStatus::Ready`.
+    /// If None, every PIN is rejected.
+    pub sim_pin: Option<String>,
+
+    /// Response for `restart_modem`.
+    pub modem_restart: Option<ModemRestartResponse>,
+
+    /// Response for `send_mms`, when the capabilities support MMS.
+    pub mms_send: Option<HttpSmsSendResponse>,
+}
+
+#[derive(Default)]
+struct MockState {
+    responses: MockResponses,
+    send_handler: Option<MockSendHandler>,
+    sent: Vec<SmsOutgoingMessage>,
+    sent_mms: Vec<HttpOutgoingMmsMessage>,
+    gnss_enabled: Option<bool>,
+    gnss_report_interval: Option<std::time::Duration>,
+    messages: std::collections::HashMap<String, Vec<SmsMessage>>,
+    delivery_reports: std::collections::HashMap<i64, Vec<SmsDeliveryReport>>,
+    friendly_names: std::collections::HashMap<String, String>,
+    callbacks: Vec<MockEventCallback>,
+}
+
+/// A mock implementing the same API as `HttpClient` (via `SmsApi`) and the
+/// `WebSocketClient` callback registration, backed by in-memory stores.
+///
+/// # Example
+/// ```
+/// use sms_client::http::SmsApi;
+/// use sms_client::mock::MockSmsClient;
+/// use sms_client::types::events::Event;
+///
+/// let mock = MockSmsClient::new();
+/// mock.on_message(|event| {
+///     if let Event::IncomingMessage(sms) = event {
+///         println!("Received: {}", sms.message_content);
+///     }
+/// });
+/// mock.set_responses(|responses| responses.version = Some("1.0.0".to_string()));
+/// ```
+#[derive(Clone, Default)]
+pub struct MockSmsClient {
+    state: std::sync::Arc<std::sync::Mutex<MockState>>,
+}
+impl MockSmsClient {
+    /// Create an empty mock client.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Modify the programmable modem and system responses.
+    pub fn set_responses<F>(&self, f: F)
+    where
+        F: FnOnce(&mut MockResponses),
+    {
+        f(&mut self.lock().responses);
+    }
+
+    /// Set the handler used to respond to `send_sms` calls. Sent messages are always
+    /// recorded, even if no handler is set (in which case sending fails).
+    pub fn on_send<F>(&self, handler: F)
+    where
+        F: Fn(&SmsOutgoingMessage) -> HttpResult<HttpSmsSendResponse> + Send + Sync + 'static,
+    {
+        self.lock().send_handler = Some(Box::new(handler));
+    }
+
+    /// Get every message passed to `send_sms`, in order.
+    #[must_use]
+    pub fn sent_messages(&self) -> Vec<SmsOutgoingMessage> {
+        self.lock().sent.clone()
+    }
+
+    /// Get every message sent as an MMS by `send_mms`, in order. Fallback SMS messages are
+    /// in `sent_messages` instead.
+    #[must_use]
+    pub fn sent_mms_messages(&self) -> Vec<HttpOutgoingMmsMessage> {
+        self.lock().sent_mms.clone()
+    }
+
+    /// Get the GNSS receiver power last set by `enable_gnss` or `disable_gnss`, if any.
+    #[must_use]
+    pub fn gnss_enabled(&self) -> Option<bool> {
+        self.lock().gnss_enabled
+    }
+
+    /// Get the GNSS report interval last set by `set_gnss_report_interval`, if any.
+    #[must_use]
+    pub fn gnss_report_interval(&self) -> Option<std::time::Duration> {
+        self.lock().gnss_report_interval
+    }
+
+    /// Store a message in the in-memory history, returned by `get_messages`.
+    pub fn push_message(&self, message: SmsMessage) {
+        self.lock()
+            .messages
+            .entry(message.phone_number.clone())
+            .or_default()
+            .push(message);
+    }
+
+    /// Store a delivery report, returned by `get_delivery_reports`.
+    pub fn push_delivery_report(&self, message_id: i64, report: SmsDeliveryReport) {
+        self.lock()
+            .delivery_reports
+            .entry(message_id)
+            .or_default()
+            .push(report);
+    }
+
+    /// Register a callback for simulated WebSocket events.
+    /// Unlike `WebSocketClient`, multiple callbacks can be registered.
+    pub fn on_message<F>(&self, callback: F)
+    where
+        F: Fn(Event) + Send + Sync + 'static,
+    {
+        self.lock().callbacks.push(std::sync::Arc::new(callback));
+    }
+
+    /// Deliver a simulated WebSocket event to every registered callback.
+    pub fn emit(&self, event: &Event) {
+        // Clone callbacks out so they can call back into the mock without deadlocking.
+        let callbacks = self.lock().callbacks.clone();
+        for callback in callbacks {
+            callback(event.clone());
+        }
+    }
+
+    /// Simulate an incoming SMS, storing it in history and emitting an `IncomingMessage` event.
+    pub fn simulate_incoming(&self, message: SmsMessage) {
+        self.push_message(message.clone());
+        self.emit(&Event::IncomingMessage(message));
+    }
+
+    /// Simulate a WebSocket connection status change.
+    pub fn simulate_connection(&self, connected: bool, reconnect: bool) {
+        self.emit(&Event::WebsocketConnectionUpdate {
+            connected,
+            reconnect,
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Record a sent message and run the send handler.
+    fn send_one(&self, message: &SmsOutgoingMessage) -> HttpResult<HttpSmsSendResponse> {
+        let mut state = self.lock();
+        state.sent.push(message.clone());
+
+        match &state.send_handler {
+            Some(handler) => handler(message),
+            None => Err(HttpError::api("No mock response set for send_sms")),
+        }
+    }
+
+    fn set_gnss_power(&self, enabled: bool) -> std::future::Ready<HttpResult<bool>> {
+        self.lock().gnss_enabled = Some(enabled);
+        std::future::ready(Ok(true))
+    }
+
+    fn respond<T: Clone>(
+        &self,
+        name: &str,
+        select: impl FnOnce(&MockResponses) -> Option<&T>,
+    ) -> std::future::Ready<HttpResult<T>> {
+        let result = select(&self.lock().responses)
+            .cloned()
+            .ok_or_else(|| HttpError::api(format!("No mock response set for {name}")));
+
+        std::future::ready(result)
+    }
+}
+impl std::fmt::Debug for MockSmsClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("MockSmsClient")
+            .field("responses", &state.responses)
+            .field("sent", &state.sent.len())
+            .field("callbacks", &state.callbacks.len())
+            .finish()
+    }
+}
+
+/// Apply offset and limit pagination options to an in-memory result set.
+fn paginate<T: Clone>(items: &[T], pagination: Option<HttpPaginationOptions>) -> Vec<T> {
+    let Some(pagination) = pagination else {
+        return items.to_vec();
+    };
+
+    let offset = usize::try_from(pagination.offset.unwrap_or(0)).unwrap_or(usize::MAX);
+    let limit = pagination.limit.map_or(usize::MAX, |limit| {
+        usize::try_from(limit).unwrap_or(usize::MAX)
+    });
+
+    items.iter().skip(offset).take(limit).cloned().collect()
+}
+
+impl SmsApi for MockSmsClient {
+    fn set_friendly_name(
+        &self,
+        phone_number: &str,
+        friendly_name: Option<&str>,
+    ) -> impl Future<Output = HttpResult<bool>> + Send {
+        let mut state = self.lock();
+        match friendly_name {
+            Some(name) => state
+                .friendly_names
+                .insert(phone_number.to_string(), name.to_string()),
+            None => state.friendly_names.remove(phone_number),
+        };
+        std::future::ready(Ok(true))
+    }
+
+    fn get_friendly_name(
+        &self,
+        phone_number: &str,
+    ) -> impl Future<Output = HttpResult<Option<String>>> + Send {
+        let name = self.lock().friendly_names.get(phone_number).cloned();
+        std::future::ready(Ok(name))
+    }
+
+    fn get_messages(
+        &self,
+        phone_number: &str,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> impl Future<Output = HttpResult<Vec<SmsMessage>>> + Send {
+        let state = self.lock();
+        let messages = state
+            .messages
+            .get(phone_number)
+            .map(|messages| paginate(messages, pagination))
+            .unwrap_or_default();
+
+        std::future::ready(Ok(messages))
+    }
+
+    fn get_latest_numbers(
+        &self,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> impl Future<Output = HttpResult<Vec<LatestNumberFriendlyNamePair>>> + Send {
+        let numbers = paginate(&self.lock().responses.latest_numbers, pagination);
+        std::future::ready(Ok(numbers))
+    }
+
+    fn get_delivery_reports(
+        &self,
+        message_id: i64,
+        pagination: Option<HttpPaginationOptions>,
+    ) -> impl Future<Output = HttpResult<Vec<SmsDeliveryReport>>> + Send {
+        let state = self.lock();
+        let reports = state
+            .delivery_reports
+            .get(&message_id)
+            .map(|reports| paginate(reports, pagination))
+            .unwrap_or_default();
+
+        std::future::ready(Ok(reports))
+    }
+
+    fn send_sms(
+        &self,
+        message: &SmsOutgoingMessage,
+    ) -> impl Future<Output = HttpResult<HttpSmsSendResponse>> + Send {
+        std::future::ready(self.send_one(message))
+    }
+
+    fn send_sms_with_meta(
+        &self,
+        message: &SmsOutgoingMessage,
+    ) -> impl Future<Output = HttpResult<WithMeta<HttpSmsSendResponse>>> + Send {
+        let result = self.send_one(message).map(|value| WithMeta {
+            value,
+            meta: ResponseMeta {
+                status: 200,
+                ..ResponseMeta::default()
+            },
+        });
+        std::future::ready(result)
+    }
+
+    fn send_sms_batch(
+        &self,
+        messages: &[SmsOutgoingMessage],
+    ) -> impl Future<Output = Vec<HttpResult<HttpSmsSendResponse>>> + Send {
+        let results = messages
+            .iter()
+            .map(|message| self.send_one(message))
+            .collect();
+        std::future::ready(results)
+    }
+
+    fn send_mms(
+        &self,
+        message: &HttpOutgoingMmsMessage,
+        capabilities: &ServerCapabilities,
+    ) -> impl Future<Output = HttpResult<MmsSendResult>> + Send {
+        if !capabilities.supports_mms() {
+            let result = self
+                .send_one(&message.to_fallback_sms())
+                .map(MmsSendResult::SmsFallback);
+            return std::future::ready(result);
+        }
+
+        let mut state = self.lock();
+        state.sent_mms.push(message.clone());
+        let result = state
+            .responses
+            .mms_send
+            .clone()
+            .map(MmsSendResult::Mms)
+            .ok_or_else(|| HttpError::api("No mock response set for send_mms"));
+        std::future::ready(result)
+    }
+
+    fn get_network_status(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemNetworkStatusResponse>> + Send {
+        self.respond("get_network_status", |r| r.network_status.as_ref())
+    }
+
+    fn get_signal_strength(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemSignalStrengthResponse>> + Send {
+        self.respond("get_signal_strength", |r| r.signal_strength.as_ref())
+    }
+
+    fn get_network_operator(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemNetworkOperatorResponse>> + Send {
+        self.respond("get_network_operator", |r| r.network_operator.as_ref())
+    }
+
+    fn get_service_provider(&self) -> impl Future<Output = HttpResult<String>> + Send {
+        self.respond("get_service_provider", |r| r.service_provider.as_ref())
+    }
+
+    fn get_battery_level(
+        &self,
+    ) -> impl Future<Output = HttpResult<HttpModemBatteryLevelResponse>> + Send {
+        self.respond("get_battery_level", |r| r.battery_level.as_ref())
+    }
+
+    fn get_gnss_status(&self) -> impl Future<Output = HttpResult<FixStatus>> + Send {
+        self.respond("get_gnss_status", |r| r.gnss_status.as_ref())
+    }
+
+    fn get_gnss_location(&self) -> impl Future<Output = HttpResult<PositionReport>> + Send {
+        self.respond("get_gnss_location", |r| r.gnss_location.as_ref())
+    }
+
+    fn enable_gnss(&self) -> impl Future<Output = HttpResult<bool>> + Send {
+        self.set_gnss_power(true)
+    }
+
+    fn disable_gnss(&self) -> impl Future<Output = HttpResult<bool>> + Send {
+        self.set_gnss_power(false)
+    }
+
+    fn set_gnss_report_interval(
+        &self,
+        interval: std::time::Duration,
+    ) -> impl Future<Output = HttpResult<bool>> + Send {
+        self.lock().gnss_report_interval = Some(interval);
+        std::future::ready(Ok(true))
+    }
+
+    fn get_pin_status(&self) -> impl Future<Output = HttpResult<SimPinStatus>> + Send {
+        self.respond("get_pin_status", |r| r.pin_status.as_ref())
+    }
+
+    fn unlock_sim(&self, pin: &str) -> impl Future<Output = HttpResult<bool>> + Send {
+        let mut state = self.lock();
+        let accepted = state.responses.sim_pin.as_deref() == Some(pin);
+        if accepted {
+            state.responses.pin_status = Some(SimPinStatus::Ready);
+        }
+        std::future::ready(Ok(accepted))
+    }
+
+    fn restart_modem(&self) -> impl Future<Output = HttpResult<ModemRestartResponse>> + Send {
+        self.respond("restart_modem", |r| r.modem_restart.as_ref())
+    }
+
+    fn get_device_info(&self) -> impl Future<Output = HttpResult<HttpSmsDeviceInfoData>> + Send {
+        self.respond("get_device_info", |r| r.device_info.as_ref())
+    }
+
+    fn get_phone_number(&self) -> impl Future<Output = HttpResult<Option<String>>> + Send {
+        let phone_number = self.lock().responses.phone_number.clone();
+        std::future::ready(Ok(phone_number))
+    }
+
+    fn get_version(&self) -> impl Future<Output = HttpResult<String>> + Send {
+        self.respond("get_version", |r| r.version.as_ref())
+    }
+
+    fn get_capabilities(&self) -> impl Future<Output = HttpResult<ServerCapabilities>> + Send {
+        let result = self
+            .respond("get_version", |r| r.version.as_ref())
+            .into_inner()
+            .map(|version| ServerCapabilities::from_version(&version));
+        std::future::ready(result)
+    }
+}
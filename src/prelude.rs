@@ -0,0 +1,28 @@
+//! Commonly used types in one import, `use sms_client::prelude::*;`.
+//! These names are kept stable even if the modules they live in are reorganized.
+
+pub use crate::Client;
+pub use crate::config::{ClientConfig, TLSConfig};
+pub use crate::error::{ClientError, ClientResult};
+pub use crate::types::events::Event;
+pub use crate::types::http::{HttpPaginationOptions, HttpSmsSendResponse};
+pub use crate::types::sms::{SmsDeliveryReport, SmsMessage, SmsOutgoingMessage};
+
+#[cfg(feature = "http")]
+pub use crate::config::HttpConfig;
+
+#[cfg(feature = "http")]
+pub use crate::http::{
+    HttpClient, SmsApi,
+    error::{HttpError, HttpResult},
+    paginator::HttpPaginator,
+};
+
+#[cfg(feature = "websocket")]
+pub use crate::config::WebSocketConfig;
+
+#[cfg(feature = "websocket")]
+pub use crate::ws::{WebSocketClient, WebsocketError, WebsocketResult};
+
+#[cfg(feature = "mock")]
+pub use crate::mock::MockSmsClient;
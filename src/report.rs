@@ -0,0 +1,241 @@
+//! Capability reporting, describing which optional parts of the client are active.
+
+/// Cargo features this crate was compiled with.
+#[derive(serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct CompiledFeatures {
+    /// The `http` feature, providing `HttpClient`.
+    pub http: bool,
+
+    /// The `websocket` feature, providing `WebSocketClient`.
+    pub websocket: bool,
+
+    /// The `http-tls-rustls` feature.
+    pub http_tls_rustls: bool,
+
+    /// The `http-tls-native` feature.
+    pub http_tls_native: bool,
+
+    /// The `websocket-tls-rustls` feature.
+    pub websocket_tls_rustls: bool,
+
+    /// The `websocket-tls-native` feature.
+    pub websocket_tls_native: bool,
+
+    /// The `http2` feature, enabling HTTP/2 for the HTTP client.
+    pub http2: bool,
+
+    /// The `compression` feature, accepting compressed HTTP responses.
+    pub compression: bool,
+
+    /// The `mock` feature, providing `MockSmsClient`.
+    pub mock: bool,
+
+    /// The `blocking` feature, providing the synchronous `blocking::Client`.
+    pub blocking: bool,
+
+    /// The `tracing` feature, instrumenting HTTP requests and the WebSocket worker.
+    pub tracing: bool,
+
+    /// The `webhook` feature, providing `WebhookReceiver`.
+    pub webhook: bool,
+
+    /// The `ffi` feature, exposing a C ABI.
+    pub ffi: bool,
+
+    /// The `simd-json` feature.
+    pub simd_json: bool,
+
+    /// The `interop` feature, providing format conversions.
+    pub interop: bool,
+
+    /// The `chrono` feature, providing timestamp conversions.
+    pub chrono: bool,
+
+    /// The `discovery` feature, providing mDNS gateway discovery.
+    pub discovery: bool,
+}
+impl CompiledFeatures {
+    /// Get the features of the current build.
+    #[must_use]
+    pub const fn current() -> Self {
+        Self {
+            http: cfg!(feature = "http"),
+            websocket: cfg!(feature = "websocket"),
+            http_tls_rustls: cfg!(feature = "http-tls-rustls"),
+            http_tls_native: cfg!(feature = "http-tls-native"),
+            websocket_tls_rustls: cfg!(feature = "websocket-tls-rustls"),
+            websocket_tls_native: cfg!(feature = "websocket-tls-native"),
+            http2: cfg!(feature = "http2"),
+            compression: cfg!(feature = "compression"),
+            mock: cfg!(feature = "mock"),
+            blocking: cfg!(feature = "blocking"),
+            tracing: cfg!(feature = "tracing"),
+            webhook: cfg!(feature = "webhook"),
+            ffi: cfg!(feature = "ffi"),
+            simd_json: cfg!(feature = "simd-json"),
+            interop: cfg!(feature = "interop"),
+            chrono: cfg!(feature = "chrono"),
+            discovery: cfg!(feature = "discovery"),
+        }
+    }
+}
+
+/// Components that were enabled in the `ClientConfig`.
+#[derive(serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ConfiguredComponents {
+    /// An HTTP client is configured.
+    pub http: bool,
+
+    /// A WebSocket client is configured.
+    pub websocket: bool,
+
+    /// A custom TLS configuration is set.
+    pub tls: bool,
+
+    /// Traffic is being recorded to a debug dump.
+    pub debug_dump: bool,
+
+    /// HTTP requests are rate limited.
+    pub rate_limit: bool,
+
+    /// HTTP requests go through a circuit breaker.
+    pub circuit_breaker: bool,
+
+    /// The number of concurrent HTTP requests is limited.
+    pub max_concurrent_requests: bool,
+
+    /// Sends are blocked while the SIM is roaming.
+    pub block_sends_while_roaming: bool,
+
+    /// A SIM PIN is configured for unlocking.
+    pub sim_pin: bool,
+
+    /// Modem responses are cached.
+    pub modem_cache: bool,
+}
+impl ConfiguredComponents {
+    pub(crate) fn from_config(config: &crate::config::ClientConfig) -> Self {
+        #[cfg_attr(not(any(feature = "http", feature = "websocket")), allow(unused_mut))]
+        let mut components = Self {
+            tls: config.tls.is_some(),
+            debug_dump: config.debug_dump.is_some(),
+            ..Default::default()
+        };
+
+        #[cfg(feature = "http")]
+        if let Some(http) = &config.http {
+            components.http = true;
+            components.rate_limit = http.rate_limit.is_some();
+            components.circuit_breaker = http.circuit_breaker.is_some();
+            components.max_concurrent_requests = http.max_concurrent_requests.is_some();
+            components.block_sends_while_roaming = http.block_sends_while_roaming;
+            components.sim_pin = http.sim_pin.is_some();
+            components.modem_cache = http.modem_cache_ttl.is_some();
+        }
+
+        #[cfg(feature = "websocket")]
+        {
+            components.websocket = config.websocket.is_some();
+        }
+
+        components
+    }
+}
+
+/// A structured summary of the active features, configuration and server,
+/// suitable for startup logs and bug reports. Contains no secrets.
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct FeatureReport {
+    /// The crate version.
+    pub client_version: &'static str,
+
+    /// Features this crate was compiled with.
+    pub compiled: CompiledFeatures,
+
+    /// Components enabled in the client configuration.
+    pub configured: ConfiguredComponents,
+
+    /// The SMS-API server version, if HTTP is configured and the server could be reached.
+    pub server_version: Option<String>,
+}
+
+/// A diagnostic snapshot of the client, written by `Client::generate_support_bundle`
+/// to attach to bug reports. Credentials are never included.
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct SupportBundle {
+    /// When the bundle was generated, in milliseconds since the UNIX epoch.
+    pub generated_at_ms: u128,
+
+    /// The feature report, including the server version.
+    pub features: FeatureReport,
+
+    /// The HTTP base URL, with any username or password removed.
+    pub http_url: Option<String>,
+
+    /// The last-known modem state.
+    pub last_known_state: crate::state::LastKnownState,
+
+    /// The cumulative counters.
+    pub counters: crate::counters::CounterSnapshot,
+
+    /// The most recent connection and parse errors.
+    pub recent_errors: crate::counters::RecentErrors,
+}
+
+/// The outcome of a single health check.
+#[derive(serde::Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "status", content = "error", rename_all = "snake_case")]
+pub enum HealthCheck {
+    /// The check passed.
+    Healthy,
+
+    /// The check failed, with a description of why.
+    Unhealthy(String),
+
+    /// The component needed for the check is not configured.
+    NotConfigured,
+}
+impl HealthCheck {
+    /// Check if this didn't fail, either passing or not being configured.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        !matches!(self, Self::Unhealthy(_))
+    }
+}
+
+/// The result of `Client::health_check`, suitable for readiness probes.
+#[derive(serde::Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct HealthReport {
+    /// The SMS-API HTTP server responded.
+    pub http_reachable: HealthCheck,
+
+    /// The authorization token was accepted.
+    pub auth_valid: HealthCheck,
+
+    /// The WebSocket is connected.
+    pub websocket_connected: HealthCheck,
+
+    /// The modem is registered to a network.
+    pub modem_online: HealthCheck,
+}
+impl HealthReport {
+    /// Check if every configured component is healthy.
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.http_reachable.is_ok()
+            && self.auth_valid.is_ok()
+            && self.websocket_connected.is_ok()
+            && self.modem_online.is_ok()
+    }
+}
+
+/// Remove any credentials from a URL.
+#[cfg(feature = "http")]
+pub(crate) fn redact_url(url: &url::Url) -> String {
+    let mut url = url.clone();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+    url.to_string()
+}
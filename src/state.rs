@@ -0,0 +1,138 @@
+//! Cache of the last-known modem state, updated from both HTTP and WebSocket paths.
+
+use sms_types::gnss::{FixStatus, PositionReport};
+use sms_types::http::{
+    HttpModemBatteryLevelResponse, HttpModemNetworkOperatorResponse,
+    HttpModemNetworkStatusResponse, HttpModemSignalStrengthResponse,
+};
+
+/// A cached value alongside the time it was last updated.
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct Timestamped<T> {
+    /// The cached value.
+    pub value: T,
+
+    /// When the value was received.
+    pub updated_at: std::time::SystemTime,
+
+    /// The cache revision taken when the value was requested or received, later requests and
+    /// events have higher revisions. This is zero for WebSocket connection updates.
+    pub revision: u64,
+}
+impl<T> Timestamped<T> {
+    /// Wrap a value, timestamping it with the current time.
+    #[must_use]
+    pub fn now(value: T) -> Self {
+        Self::at_revision(value, 0)
+    }
+
+    /// Wrap a value from a request started at a cache revision, timestamping it with the current time.
+    #[must_use]
+    pub fn at_revision(value: T, revision: u64) -> Self {
+        Self {
+            value,
+            updated_at: std::time::SystemTime::now(),
+            revision,
+        }
+    }
+
+    /// Get how long ago the value was updated.
+    /// If the system clock has gone backwards, this is zero.
+    #[must_use]
+    pub fn age(&self) -> std::time::Duration {
+        self.updated_at.elapsed().unwrap_or_default()
+    }
+}
+
+/// A snapshot of the last-known modem state. Each value is None until
+/// it has been received at least once.
+#[derive(serde::Serialize, Clone, Debug, Default)]
+pub struct LastKnownState {
+    /// The last carrier network status.
+    pub network_status: Option<Timestamped<HttpModemNetworkStatusResponse>>,
+
+    /// The last signal strength sample.
+    pub signal_strength: Option<Timestamped<HttpModemSignalStrengthResponse>>,
+
+    /// The last network operator.
+    pub network_operator: Option<Timestamped<HttpModemNetworkOperatorResponse>>,
+
+    /// The last battery reading.
+    pub battery_level: Option<Timestamped<HttpModemBatteryLevelResponse>>,
+
+    /// The last modem status, from WebSocket status update events.
+    pub modem_status: Option<Timestamped<sms_types::events::ModemStatusUpdateState>>,
+
+    /// The last GNSS fix status.
+    pub gnss_status: Option<Timestamped<FixStatus>>,
+
+    /// The last GNSS position.
+    pub gnss_location: Option<Timestamped<PositionReport>>,
+
+    /// The last WebSocket connection status.
+    pub websocket_connected: Option<Timestamped<bool>>,
+}
+
+/// A shared handle to the last-known state, cheap to clone.
+#[derive(Clone, Debug, Default)]
+pub struct StateCache {
+    state: std::sync::Arc<std::sync::RwLock<LastKnownState>>,
+    revision: std::sync::Arc<std::sync::atomic::AtomicU64>,
+}
+impl StateCache {
+    /// Get a copy of the current last-known state.
+    #[must_use]
+    pub fn snapshot(&self) -> LastKnownState {
+        match self.state.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Read from the last-known state without copying it.
+    pub(crate) fn read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&LastKnownState) -> R,
+    {
+        match self.state.read() {
+            Ok(guard) => f(&guard),
+            Err(poisoned) => f(&poisoned.into_inner()),
+        }
+    }
+
+    /// Modify the last-known state in place.
+    pub(crate) fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&mut LastKnownState),
+    {
+        match self.state.write() {
+            Ok(mut guard) => f(&mut guard),
+            Err(poisoned) => f(&mut poisoned.into_inner()),
+        }
+    }
+
+    /// Take the next revision, which should be done before a request is sent so responses
+    /// that complete out of order can be detected with `update_newer`.
+    pub(crate) fn next_revision(&self) -> u64 {
+        self.revision
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+            .wrapping_add(1)
+    }
+
+    /// Set a cached value from a request, unless the value was already updated by a
+    /// request that started later (so a slow response never overwrites fresher data).
+    pub(crate) fn update_newer<T, F>(&self, revision: u64, field: F, value: T)
+    where
+        F: FnOnce(&mut LastKnownState) -> &mut Option<Timestamped<T>>,
+    {
+        self.update(|state| {
+            let field = field(state);
+            if field
+                .as_ref()
+                .is_none_or(|current| current.revision < revision)
+            {
+                *field = Some(Timestamped::at_revision(value, revision));
+            }
+        });
+    }
+}
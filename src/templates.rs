@@ -0,0 +1,342 @@
+//! Named message templates with `{placeholder}` substitution, validated before sending.
+//!
+//! # Example
+//! ```
+//! use sms_client::templates::{TemplateRegistry, TemplateValues};
+//!
+//! let mut templates = TemplateRegistry::new();
+//! templates.register("otp", "Your code is {code}, it expires in {minutes} minutes").unwrap();
+//!
+//! let rendered = templates
+//!     .render("otp", &TemplateValues::new().with("code", 482_913).with("minutes", 10))
+//!     .unwrap();
+//! assert_eq!(rendered.content, "Your code is 482913, it expires in 10 minutes");
+//!
+//! let message = rendered.to_message("+447700900000");
+//! ```
+
+use crate::types::sms_encoding::{SmsEncoding, SmsEncodingInfo};
+
+/// An error parsing or rendering a template.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// A `{` was not closed by a matching `}`.
+    #[error("Unclosed placeholder starting at byte {0}")]
+    UnclosedPlaceholder(usize),
+
+    /// A `}` appeared without an opening `{`. Literal braces are written as `{{` and `}}`.
+    #[error("Unmatched '}}' at byte {0}")]
+    UnmatchedBrace(usize),
+
+    /// A placeholder has no name, eg: `{}`.
+    #[error("Empty placeholder at byte {0}")]
+    EmptyPlaceholder(usize),
+
+    /// No template is registered with the name.
+    #[error("Unknown template '{0}'")]
+    UnknownTemplate(String),
+
+    /// No value was given for a placeholder.
+    #[error("Missing value for placeholder '{0}'")]
+    MissingValue(String),
+
+    /// The rendered content needs more segments than the template allows.
+    #[error("Rendered message needs {segments} segments, the limit is {max}")]
+    TooManySegments {
+        /// The number of segments the rendered content needs.
+        segments: usize,
+        /// The template segment limit.
+        max: usize,
+    },
+
+    /// The template requires GSM-7, but the rendered content contains other characters.
+    #[error("Rendered message contains non GSM-7 characters: {0:?}")]
+    NotGsm7(Vec<char>),
+}
+
+/// Result type alias for template operations.
+pub type TemplateResult<T> = Result<T, TemplateError>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TemplatePart {
+    Text(String),
+    Placeholder(String),
+}
+
+/// A parsed message template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SmsTemplate {
+    parts: Vec<TemplatePart>,
+
+    /// Optional maximum number of segments the rendered content may use.
+    pub max_segments: Option<usize>,
+
+    /// Reject rendered content that would be sent as UCS-2, eg: because of a substituted emoji.
+    pub require_gsm7: bool,
+}
+impl SmsTemplate {
+    /// Parse a template, where `{name}` is a placeholder and `{{` / `}}` are literal braces.
+    pub fn parse(template: &str) -> TemplateResult<Self> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.char_indices().peekable();
+
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '{' if chars.next_if(|&(_, next)| next == '{').is_some() => text.push('{'),
+                '}' if chars.next_if(|&(_, next)| next == '}').is_some() => text.push('}'),
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) => break,
+                            Some((_, c)) => name.push(c),
+                            None => return Err(TemplateError::UnclosedPlaceholder(index)),
+                        }
+                    }
+
+                    let name = name.trim();
+                    if name.is_empty() {
+                        return Err(TemplateError::EmptyPlaceholder(index));
+                    }
+                    if !text.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(TemplatePart::Placeholder(name.to_string()));
+                }
+                '}' => return Err(TemplateError::UnmatchedBrace(index)),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+
+        Ok(Self {
+            parts,
+            max_segments: None,
+            require_gsm7: false,
+        })
+    }
+
+    /// Limit the number of segments the rendered content may use.
+    #[must_use]
+    pub fn with_max_segments(mut self, max_segments: usize) -> Self {
+        self.max_segments = Some(max_segments);
+        self
+    }
+
+    /// Reject rendered content that can't be sent as GSM-7.
+    #[must_use]
+    pub fn with_require_gsm7(mut self, require_gsm7: bool) -> Self {
+        self.require_gsm7 = require_gsm7;
+        self
+    }
+
+    /// Get the placeholder names, in the order they appear.
+    pub fn placeholders(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().filter_map(|part| match part {
+            TemplatePart::Placeholder(name) => Some(name.as_str()),
+            TemplatePart::Text(_) => None,
+        })
+    }
+
+    /// Substitute every placeholder, then validate the content length and encoding.
+    pub fn render(&self, values: &TemplateValues) -> TemplateResult<RenderedSms> {
+        let mut content = String::new();
+        for part in &self.parts {
+            match part {
+                TemplatePart::Text(text) => content.push_str(text),
+                TemplatePart::Placeholder(name) => content.push_str(
+                    values
+                        .get(name)
+                        .ok_or_else(|| TemplateError::MissingValue(name.clone()))?,
+                ),
+            }
+        }
+
+        let encoding = SmsEncodingInfo::analyze(&content);
+        if self.require_gsm7 && encoding.encoding != SmsEncoding::Gsm7 {
+            return Err(TemplateError::NotGsm7(
+                crate::types::sms_encoding::non_gsm7_characters(&content),
+            ));
+        }
+        if let Some(max) = self.max_segments
+            && encoding.segments > max
+        {
+            return Err(TemplateError::TooManySegments {
+                segments: encoding.segments,
+                max,
+            });
+        }
+
+        Ok(RenderedSms { content, encoding })
+    }
+}
+
+/// Placeholder values to render a template with.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TemplateValues(std::collections::BTreeMap<String, String>);
+impl TemplateValues {
+    /// Create an empty set of values.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a placeholder value, formatted with `Display`.
+    #[must_use]
+    pub fn with(mut self, name: impl Into<String>, value: impl std::fmt::Display) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Set a placeholder value, formatted with `Display`.
+    pub fn set(&mut self, name: impl Into<String>, value: impl std::fmt::Display) {
+        self.0.insert(name.into(), value.to_string());
+    }
+
+    /// Get a placeholder value.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+}
+
+/// Validated content rendered from a template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenderedSms {
+    /// The rendered message content.
+    pub content: String,
+
+    /// The encoding and number of segments the content will use.
+    pub encoding: SmsEncodingInfo,
+}
+impl RenderedSms {
+    /// Create an outgoing message with the rendered content.
+    #[must_use]
+    pub fn to_message(&self, to: &str) -> sms_types::sms::SmsOutgoingMessage {
+        sms_types::sms::SmsOutgoingMessage::simple_message(to, &self.content)
+    }
+}
+
+/// A set of named templates.
+#[derive(Clone, Debug, Default)]
+pub struct TemplateRegistry {
+    templates: std::collections::HashMap<String, SmsTemplate>,
+}
+impl TemplateRegistry {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse and register a template, replacing any existing template with the same name.
+    pub fn register(&mut self, name: impl Into<String>, template: &str) -> TemplateResult<()> {
+        self.insert(name, SmsTemplate::parse(template)?);
+        Ok(())
+    }
+
+    /// Register an already parsed template, eg: one with validation limits set.
+    pub fn insert(&mut self, name: impl Into<String>, template: SmsTemplate) {
+        self.templates.insert(name.into(), template);
+    }
+
+    /// Get a registered template.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&SmsTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Render a registered template.
+    pub fn render(&self, name: &str, values: &TemplateValues) -> TemplateResult<RenderedSms> {
+        self.templates
+            .get(name)
+            .ok_or_else(|| TemplateError::UnknownTemplate(name.to_string()))?
+            .render(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors_report_the_byte_offset() {
+        assert_eq!(
+            SmsTemplate::parse("Hi {name"),
+            Err(TemplateError::UnclosedPlaceholder(3))
+        );
+        assert_eq!(
+            SmsTemplate::parse("Hi }"),
+            Err(TemplateError::UnmatchedBrace(3))
+        );
+        assert_eq!(
+            SmsTemplate::parse("Hi {}"),
+            Err(TemplateError::EmptyPlaceholder(3))
+        );
+        assert_eq!(
+            SmsTemplate::parse("{  }"),
+            Err(TemplateError::EmptyPlaceholder(0))
+        );
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        let template = SmsTemplate::parse("{{code}} is {code}").unwrap();
+        assert_eq!(template.placeholders().collect::<Vec<_>>(), vec!["code"]);
+
+        let rendered = template
+            .render(&TemplateValues::new().with("code", 42))
+            .unwrap();
+        assert_eq!(rendered.content, "{code} is 42");
+    }
+
+    #[test]
+    fn placeholder_names_are_trimmed() {
+        let template = SmsTemplate::parse("Hello { name }!").unwrap();
+        let rendered = template
+            .render(&TemplateValues::new().with("name", "Alex"))
+            .unwrap();
+        assert_eq!(rendered.content, "Hello Alex!");
+    }
+
+    #[test]
+    fn missing_values_are_rejected() {
+        let template = SmsTemplate::parse("{greeting} {name}").unwrap();
+        assert_eq!(
+            template.render(&TemplateValues::new().with("greeting", "Hi")),
+            Err(TemplateError::MissingValue("name".to_string()))
+        );
+    }
+
+    #[test]
+    fn rendered_content_is_validated() {
+        let template = SmsTemplate::parse("{value}")
+            .unwrap()
+            .with_require_gsm7(true)
+            .with_max_segments(1);
+
+        assert_eq!(
+            template.render(&TemplateValues::new().with("value", "Hi 👋")),
+            Err(TemplateError::NotGsm7(vec!['👋']))
+        );
+        assert_eq!(
+            template.render(&TemplateValues::new().with("value", "a".repeat(161))),
+            Err(TemplateError::TooManySegments {
+                segments: 2,
+                max: 1
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_templates_are_rejected() {
+        let registry = TemplateRegistry::new();
+        assert_eq!(
+            registry.render("otp", &TemplateValues::new()),
+            Err(TemplateError::UnknownTemplate("otp".to_string()))
+        );
+    }
+}
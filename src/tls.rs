@@ -0,0 +1,237 @@
+//! Certificate fingerprint pinning, shared by the HTTP and WebSocket rustls connections.
+//! A pinned fingerprint can replace CA validation, or be checked in addition to it.
+//! Client identities for mutual TLS are also loaded here.
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use sha2::Digest;
+
+/// A client certificate chain and private key.
+pub(crate) type ClientIdentityDer = (Vec<CertificateDer<'static>>, PrivateKeyDer<'static>);
+
+/// Accepts only a server certificate matching a SHA-256 fingerprint, and that also passes
+/// CA validation if there is a root certificate.
+#[derive(Debug)]
+struct PinnedCertVerifier {
+    fingerprint: [u8; 32],
+    ca_verifier: Option<std::sync::Arc<rustls::client::WebPkiServerCertVerifier>>,
+    provider: std::sync::Arc<rustls::crypto::CryptoProvider>,
+}
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        if sha2::Sha256::digest(end_entity.as_ref()).as_slice() != self.fingerprint {
+            return Err(rustls::Error::General(
+                "Server certificate does not match the pinned fingerprint".to_string(),
+            ));
+        }
+
+        match &self.ca_verifier {
+            Some(ca_verifier) => ca_verifier.verify_server_cert(
+                end_entity,
+                intermediates,
+                server_name,
+                ocsp_response,
+                now,
+            ),
+            None => Ok(ServerCertVerified::assertion()),
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+/// Accepts any server certificate, while still checking handshake signatures.
+#[cfg(feature = "websocket-tls-rustls")]
+#[derive(Debug)]
+struct AcceptAnyCertVerifier {
+    provider: std::sync::Arc<rustls::crypto::CryptoProvider>,
+}
+#[cfg(feature = "websocket-tls-rustls")]
+impl ServerCertVerifier for AcceptAnyCertVerifier {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+/// Create a rustls client config that accepts any server certificate.
+#[cfg(feature = "websocket-tls-rustls")]
+pub(crate) fn insecure_client_config(
+    identity: Option<ClientIdentityDer>,
+) -> Result<rustls::ClientConfig, rustls::Error> {
+    let provider = std::sync::Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let verifier = AcceptAnyCertVerifier {
+        provider: std::sync::Arc::clone(&provider),
+    };
+
+    let builder = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .dangerous()
+        .with_custom_certificate_verifier(std::sync::Arc::new(verifier));
+
+    match identity {
+        Some((chain, key)) => builder.with_client_auth_cert(chain, key),
+        None => Ok(builder.with_no_client_auth()),
+    }
+}
+
+/// Create a rustls client config that trusts only the certificate with `fingerprint`.
+/// With a root certificate, the server certificate must also be signed by it.
+pub(crate) fn pinned_client_config(
+    fingerprint: [u8; 32],
+    root_certificate: Option<CertificateDer<'static>>,
+    identity: Option<ClientIdentityDer>,
+) -> Result<rustls::ClientConfig, rustls::Error> {
+    let provider = std::sync::Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+
+    let ca_verifier = match root_certificate {
+        Some(certificate) => {
+            let mut root_store = rustls::RootCertStore::empty();
+            root_store.add(certificate)?;
+
+            let verifier = rustls::client::WebPkiServerCertVerifier::builder_with_provider(
+                std::sync::Arc::new(root_store),
+                std::sync::Arc::clone(&provider),
+            )
+            .build()
+            .map_err(|e| rustls::Error::General(e.to_string()))?;
+            Some(verifier)
+        }
+        None => None,
+    };
+
+    let verifier = PinnedCertVerifier {
+        fingerprint,
+        ca_verifier,
+        provider: std::sync::Arc::clone(&provider),
+    };
+
+    let builder = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .dangerous()
+        .with_custom_certificate_verifier(std::sync::Arc::new(verifier));
+
+    match identity {
+        Some((chain, key)) => builder.with_client_auth_cert(chain, key),
+        None => Ok(builder.with_no_client_auth()),
+    }
+}
+
+/// Read a PEM or DER certificate.
+pub(crate) fn read_certificate(
+    source: &crate::config::CertificateSource,
+) -> Result<CertificateDer<'static>, String> {
+    let (data, _) = source
+        .read()
+        .map_err(|e| format!("Failed to read certificate file: {e}"))?;
+    if !data.starts_with(b"-----BEGIN") {
+        return Ok(CertificateDer::from(data.into_owned()));
+    }
+
+    rustls_pemfile::certs(&mut data.as_ref())
+        .next()
+        .ok_or_else(|| "No certificate found in PEM file".to_string())?
+        .map_err(|e| format!("Failed to parse PEM certificate: {e}"))
+}
+
+/// Read a client identity's PEM certificate chain and PEM private key.
+pub(crate) fn read_identity(
+    identity: &crate::config::ClientIdentity,
+) -> Result<ClientIdentityDer, String> {
+    let certificate_data = std::fs::read(&identity.certificate)
+        .map_err(|e| format!("Failed to read client certificate file: {e}"))?;
+    let chain = rustls_pemfile::certs(&mut certificate_data.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse client certificate: {e}"))?;
+    if chain.is_empty() {
+        return Err("No certificate found in client certificate file".to_string());
+    }
+
+    let key_data =
+        std::fs::read(&identity.key).map_err(|e| format!("Failed to read client key file: {e}"))?;
+    let key = rustls_pemfile::private_key(&mut key_data.as_slice())
+        .map_err(|e| format!("Failed to parse client key: {e}"))?
+        .ok_or_else(|| "No private key found in client key file".to_string())?;
+
+    Ok((chain, key))
+}
@@ -0,0 +1,143 @@
+//! Typed delivery report status, decoded from the raw GSM TP-Status octet.
+
+/// The broad outcome a TP-Status value belongs to.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeliveryStatusGroup {
+    /// The message was delivered, or the transaction otherwise completed.
+    Completed,
+
+    /// A temporary error occurred, and the SMSC is still trying to deliver.
+    TemporaryFailureRetrying,
+
+    /// A permanent error occurred, and the SMSC has given up.
+    PermanentFailure,
+
+    /// A temporary error occurred, but the SMSC is no longer trying to deliver.
+    TemporaryFailureStopped,
+}
+impl DeliveryStatusGroup {
+    /// Get the group for a raw TP-Status value.
+    #[must_use]
+    pub fn from_tp_status(status: u8) -> Self {
+        match status & 0x7F {
+            0x00..=0x1F => Self::Completed,
+            0x20..=0x3F => Self::TemporaryFailureRetrying,
+            0x40..=0x5F => Self::PermanentFailure,
+            _ => Self::TemporaryFailureStopped,
+        }
+    }
+
+    /// Check if no further delivery reports are expected for the message.
+    #[must_use]
+    pub fn is_final(self) -> bool {
+        !matches!(self, Self::TemporaryFailureRetrying)
+    }
+
+    /// Check if the message was delivered successfully.
+    #[must_use]
+    pub fn is_success(self) -> bool {
+        matches!(self, Self::Completed)
+    }
+}
+
+/// A typed TP-Status value (3GPP TS 23.040), with the common codes named.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "u8", into = "u8")]
+pub enum DeliveryStatus {
+    /// The message was received by the recipient.
+    Delivered,
+
+    /// The message was forwarded, but the SMSC couldn't confirm delivery.
+    ForwardedUnconfirmed,
+
+    /// The message was replaced by the SMSC.
+    Replaced,
+
+    /// The network is congested, delivery is still being attempted.
+    Congestion,
+
+    /// The recipient is busy, delivery is still being attempted.
+    RecipientBusy,
+
+    /// The recipient didn't respond, delivery is still being attempted.
+    NoResponse,
+
+    /// The message validity period expired before it could be delivered.
+    ValidityPeriodExpired,
+
+    /// The message was deleted by the sender.
+    DeletedBySender,
+
+    /// The message was deleted by the SMSC administration.
+    DeletedBySmsc,
+
+    /// Any other TP-Status value, see `DeliveryStatus::group` for its outcome.
+    Other(u8),
+}
+impl DeliveryStatus {
+    /// Get the broad outcome this status belongs to.
+    #[must_use]
+    pub fn group(self) -> DeliveryStatusGroup {
+        DeliveryStatusGroup::from_tp_status(self.into())
+    }
+}
+impl From<u8> for DeliveryStatus {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => Self::Delivered,
+            0x01 => Self::ForwardedUnconfirmed,
+            0x02 => Self::Replaced,
+            0x20 => Self::Congestion,
+            0x21 => Self::RecipientBusy,
+            0x22 => Self::NoResponse,
+            0x46 => Self::ValidityPeriodExpired,
+            0x47 => Self::DeletedBySender,
+            0x48 => Self::DeletedBySmsc,
+            other => Self::Other(other),
+        }
+    }
+}
+impl From<DeliveryStatus> for u8 {
+    fn from(value: DeliveryStatus) -> Self {
+        match value {
+            DeliveryStatus::Delivered => 0x00,
+            DeliveryStatus::ForwardedUnconfirmed => 0x01,
+            DeliveryStatus::Replaced => 0x02,
+            DeliveryStatus::Congestion => 0x20,
+            DeliveryStatus::RecipientBusy => 0x21,
+            DeliveryStatus::NoResponse => 0x22,
+            DeliveryStatus::ValidityPeriodExpired => 0x46,
+            DeliveryStatus::DeletedBySender => 0x47,
+            DeliveryStatus::DeletedBySmsc => 0x48,
+            DeliveryStatus::Other(other) => other,
+        }
+    }
+}
+
+/// Typed access to the raw `status` in `SmsDeliveryReport`.
+///
+/// # Example
+/// ```
+/// use sms_client::types::delivery::DeliveryReportExt;
+/// use sms_client::types::sms::SmsDeliveryReport;
+///
+/// fn was_delivered(reports: &[SmsDeliveryReport]) -> bool {
+///     reports.iter().any(|report| report.status_group().is_success())
+/// }
+/// ```
+pub trait DeliveryReportExt {
+    /// Get the typed TP-Status.
+    fn status(&self) -> DeliveryStatus;
+
+    /// Get the broad outcome of the TP-Status.
+    fn status_group(&self) -> DeliveryStatusGroup;
+}
+impl DeliveryReportExt for sms_types::sms::SmsDeliveryReport {
+    fn status(&self) -> DeliveryStatus {
+        DeliveryStatus::from(self.status)
+    }
+
+    fn status_group(&self) -> DeliveryStatusGroup {
+        DeliveryStatusGroup::from_tp_status(self.status)
+    }
+}
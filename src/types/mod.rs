@@ -0,0 +1,12 @@
+//! SMS-API wire types, re-exported from `sms_types`, alongside client-side helpers.
+
+pub use sms_types::*;
+
+pub mod delivery;
+pub mod modem;
+pub mod sms_encoding;
+pub mod v1;
+pub mod validity;
+
+#[cfg(feature = "chrono")]
+pub mod time;
@@ -0,0 +1,307 @@
+//! Typed helpers for modem state values that are sent as raw codes or strings.
+
+/// SIM lock state, as reported by the modem (`AT+CPIN?`).
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(from = "String", into = "String")]
+pub enum SimPinStatus {
+    /// The SIM is unlocked and ready.
+    Ready,
+
+    /// The SIM is waiting for its PIN.
+    SimPin,
+
+    /// The SIM is blocked and waiting for its PUK.
+    SimPuk,
+
+    /// The SIM is waiting for its PIN2.
+    SimPin2,
+
+    /// The SIM is blocked and waiting for its PUK2.
+    SimPuk2,
+
+    /// The modem is waiting for the phone-to-SIM password.
+    PhSimPin,
+
+    /// Any other status string reported by the modem.
+    Other(String),
+}
+impl SimPinStatus {
+    /// Check if the SIM must be unlocked with a PIN before it can be used.
+    #[must_use]
+    pub fn needs_pin(&self) -> bool {
+        matches!(self, Self::SimPin)
+    }
+
+    /// Check if the SIM is blocked, and can only be unlocked with a PUK.
+    #[must_use]
+    pub fn is_blocked(&self) -> bool {
+        matches!(self, Self::SimPuk | Self::SimPuk2)
+    }
+}
+impl From<String> for SimPinStatus {
+    fn from(value: String) -> Self {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "READY" => Self::Ready,
+            "SIM PIN" => Self::SimPin,
+            "SIM PUK" => Self::SimPuk,
+            "SIM PIN2" => Self::SimPin2,
+            "SIM PUK2" => Self::SimPuk2,
+            "PH-SIM PIN" => Self::PhSimPin,
+            _ => Self::Other(value),
+        }
+    }
+}
+impl From<SimPinStatus> for String {
+    fn from(value: SimPinStatus) -> Self {
+        match value {
+            SimPinStatus::Ready => "READY".to_string(),
+            SimPinStatus::SimPin => "SIM PIN".to_string(),
+            SimPinStatus::SimPuk => "SIM PUK".to_string(),
+            SimPinStatus::SimPin2 => "SIM PIN2".to_string(),
+            SimPinStatus::SimPuk2 => "SIM PUK2".to_string(),
+            SimPinStatus::PhSimPin => "PH-SIM PIN".to_string(),
+            SimPinStatus::Other(value) => value,
+        }
+    }
+}
+
+/// The modem's acknowledgement of a restart request.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ModemRestartResponse {
+    /// True if the modem accepted the restart and is going offline.
+    pub accepted: bool,
+
+    /// An optional message from the server, eg: why the restart was refused.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// The 3GPP `AT+CSQ` RSSI value meaning "not known or not detectable".
+pub const RSSI_UNKNOWN: u8 = 99;
+
+/// A signal quality rating, from the `AT+CSQ` RSSI value.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignalQuality {
+    /// -73 dBm or better.
+    Excellent,
+
+    /// -83 to -75 dBm.
+    Good,
+
+    /// -93 to -85 dBm.
+    Fair,
+
+    /// Worse than -93 dBm.
+    Poor,
+
+    /// The signal is unknown or undetectable.
+    Unknown,
+}
+impl SignalQuality {
+    /// Rate a raw `AT+CSQ` RSSI value (0-31, or 99 for unknown).
+    #[must_use]
+    pub fn from_rssi(rssi: i32) -> Self {
+        match rssi {
+            20..=31 => Self::Excellent,
+            15..=19 => Self::Good,
+            10..=14 => Self::Fair,
+            0..=9 => Self::Poor,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Conversions for the raw `AT+CSQ` values in `HttpModemSignalStrengthResponse`.
+///
+/// # Example
+/// ```
+/// use sms_client::types::modem::{SignalQuality, SignalStrengthExt};
+/// use sms_client::types::http::HttpModemSignalStrengthResponse;
+///
+/// fn describe(signal: &HttpModemSignalStrengthResponse) -> String {
+///     match signal.rssi_dbm() {
+///         Some(dbm) => format!("{dbm} dBm, {} bars ({:?})", signal.bars(), signal.quality()),
+///         None => "No signal".to_string(),
+///     }
+/// }
+/// ```
+pub trait SignalStrengthExt {
+    /// Get the RSSI in dBm, or None if the modem reported it as unknown.
+    /// The top value (31) means -51 dBm or better.
+    fn rssi_dbm(&self) -> Option<i32>;
+
+    /// Get a 0-5 bar rating, where 0 is no or unknown signal.
+    fn bars(&self) -> u8;
+
+    /// Get a signal quality rating.
+    fn quality(&self) -> SignalQuality;
+}
+impl SignalStrengthExt for sms_types::http::HttpModemSignalStrengthResponse {
+    fn rssi_dbm(&self) -> Option<i32> {
+        let rssi = i32::from(self.rssi);
+        (0..=31).contains(&rssi).then_some(-113 + rssi * 2)
+    }
+
+    fn bars(&self) -> u8 {
+        match i32::from(self.rssi) {
+            25..=31 => 5,
+            20..=24 => 4,
+            15..=19 => 3,
+            10..=14 => 2,
+            2..=9 => 1,
+            _ => 0,
+        }
+    }
+
+    fn quality(&self) -> SignalQuality {
+        SignalQuality::from_rssi(i32::from(self.rssi))
+    }
+}
+
+/// The 3GPP network registration status (`AT+CREG?`), from the raw `registration` code.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "u8", into = "u8")]
+pub enum RegistrationStatus {
+    /// Not registered, and not searching for an operator.
+    NotRegistered,
+
+    /// Registered to the home network.
+    RegisteredHome,
+
+    /// Not registered, but searching for an operator.
+    Searching,
+
+    /// Registration was denied.
+    Denied,
+
+    /// The registration state is unknown.
+    Unknown,
+
+    /// Registered to a network while roaming.
+    RegisteredRoaming,
+
+    /// Any other registration code, eg: SMS-only registration.
+    Other(u8),
+}
+impl RegistrationStatus {
+    /// Check if the modem is registered to a network, either home or roaming.
+    #[must_use]
+    pub fn is_registered(self) -> bool {
+        matches!(self, Self::RegisteredHome | Self::RegisteredRoaming)
+    }
+
+    /// Check if the modem is registered while roaming.
+    #[must_use]
+    pub fn is_roaming(self) -> bool {
+        matches!(self, Self::RegisteredRoaming)
+    }
+}
+impl From<u8> for RegistrationStatus {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::NotRegistered,
+            1 => Self::RegisteredHome,
+            2 => Self::Searching,
+            3 => Self::Denied,
+            4 => Self::Unknown,
+            5 => Self::RegisteredRoaming,
+            other => Self::Other(other),
+        }
+    }
+}
+impl From<RegistrationStatus> for u8 {
+    fn from(value: RegistrationStatus) -> Self {
+        match value {
+            RegistrationStatus::NotRegistered => 0,
+            RegistrationStatus::RegisteredHome => 1,
+            RegistrationStatus::Searching => 2,
+            RegistrationStatus::Denied => 3,
+            RegistrationStatus::Unknown => 4,
+            RegistrationStatus::RegisteredRoaming => 5,
+            RegistrationStatus::Other(other) => other,
+        }
+    }
+}
+
+/// Typed access to the raw `registration` code in `HttpModemNetworkStatusResponse`.
+pub trait NetworkStatusExt {
+    /// Get the typed network registration status.
+    fn registration_status(&self) -> RegistrationStatus;
+}
+impl NetworkStatusExt for sms_types::http::HttpModemNetworkStatusResponse {
+    fn registration_status(&self) -> RegistrationStatus {
+        RegistrationStatus::from(self.registration)
+    }
+}
+
+/// The modem battery charging status (`AT+CBC`), from the raw `status` code.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "u8", into = "u8")]
+pub enum BatteryChargingStatus {
+    /// Running from the battery.
+    NotCharging,
+
+    /// The battery is charging.
+    Charging,
+
+    /// No battery is connected.
+    NoBattery,
+
+    /// Any other status code.
+    Unknown(u8),
+}
+impl From<u8> for BatteryChargingStatus {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::NotCharging,
+            1 => Self::Charging,
+            2 => Self::NoBattery,
+            other => Self::Unknown(other),
+        }
+    }
+}
+impl From<BatteryChargingStatus> for u8 {
+    fn from(value: BatteryChargingStatus) -> Self {
+        match value {
+            BatteryChargingStatus::NotCharging => 0,
+            BatteryChargingStatus::Charging => 1,
+            BatteryChargingStatus::NoBattery => 2,
+            BatteryChargingStatus::Unknown(other) => other,
+        }
+    }
+}
+
+/// Typed access to the raw values in `HttpModemBatteryLevelResponse`.
+///
+/// # Example
+/// ```
+/// use sms_client::types::modem::BatteryLevelExt;
+/// use sms_client::types::http::HttpModemBatteryLevelResponse;
+///
+/// fn should_warn(battery: &HttpModemBatteryLevelResponse) -> bool {
+///     battery.is_low(20) && !battery.is_charging()
+/// }
+/// ```
+pub trait BatteryLevelExt {
+    /// Get the typed charging status.
+    fn charging_status(&self) -> BatteryChargingStatus;
+
+    /// Check if the battery is charging.
+    fn is_charging(&self) -> bool;
+
+    /// Check if a battery is connected and its charge percentage is at or below `threshold`.
+    fn is_low(&self, threshold: u8) -> bool;
+}
+impl BatteryLevelExt for sms_types::http::HttpModemBatteryLevelResponse {
+    fn charging_status(&self) -> BatteryChargingStatus {
+        BatteryChargingStatus::from(self.status)
+    }
+
+    fn is_charging(&self) -> bool {
+        self.charging_status() == BatteryChargingStatus::Charging
+    }
+
+    fn is_low(&self, threshold: u8) -> bool {
+        self.charging_status() != BatteryChargingStatus::NoBattery && self.charge <= threshold
+    }
+}
@@ -0,0 +1,203 @@
+//! SMS encoding and segment estimation for outgoing message content.
+//! Content that fits the GSM 03.38 alphabet is sent as GSM-7, anything else forces UCS-2.
+
+/// GSM 03.38 basic character set, each character costs one septet.
+const GSM7_BASIC: &str = "@£$¥èéùìòÇ\nØø\rÅåΔ_ΦΓΛΩΠΨΣΘΞÆæßÉ !\"#¤%&'()*+,-./0123456789:;<=>?\
+    ¡ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÑÜ§¿abcdefghijklmnopqrstuvwxyzäöñüà";
+
+/// GSM 03.38 extension table, each character costs two septets (escape + character).
+const GSM7_EXTENSION: &str = "\u{000C}^{}\\[~]|€";
+
+/// The encoding a message will be sent with.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SmsEncoding {
+    /// The GSM 03.38 7-bit default alphabet.
+    Gsm7,
+
+    /// UCS-2 (UTF-16), used when any character is outside the GSM-7 alphabet.
+    Ucs2,
+}
+impl SmsEncoding {
+    /// The number of encoding units (septets or UTF-16 code units) that fit in a single SMS.
+    #[must_use]
+    pub fn single_segment_units(self) -> usize {
+        match self {
+            Self::Gsm7 => 160,
+            Self::Ucs2 => 70,
+        }
+    }
+
+    /// The number of encoding units that fit in each part of a concatenated SMS,
+    /// which is reduced to make room for the concatenation header.
+    #[must_use]
+    pub fn multi_segment_units(self) -> usize {
+        match self {
+            Self::Gsm7 => 153,
+            Self::Ucs2 => 67,
+        }
+    }
+}
+
+/// The estimated encoding and size of some message content.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SmsEncodingInfo {
+    /// The encoding the content requires.
+    pub encoding: SmsEncoding,
+
+    /// The number of characters in the content.
+    pub characters: usize,
+
+    /// The encoded length, in septets for GSM-7 or UTF-16 code units for UCS-2.
+    pub units: usize,
+
+    /// The number of SMS segments the content will be split into.
+    pub segments: usize,
+}
+impl SmsEncodingInfo {
+    /// Analyze message content, determining its encoding and segment count.
+    ///
+    /// # Example
+    /// ```
+    /// use sms_client::types::sms_encoding::{SmsEncoding, SmsEncodingInfo};
+    ///
+    /// let info = SmsEncodingInfo::analyze("Hello!");
+    /// assert_eq!(info.encoding, SmsEncoding::Gsm7);
+    /// assert_eq!(info.segments, 1);
+    ///
+    /// // A single emoji forces the whole message into UCS-2.
+    /// let info = SmsEncodingInfo::analyze("Hello! 👋");
+    /// assert_eq!(info.encoding, SmsEncoding::Ucs2);
+    /// ```
+    #[must_use]
+    pub fn analyze(content: &str) -> Self {
+        let encoding = if content.chars().all(|c| gsm7_septets(c).is_some()) {
+            SmsEncoding::Gsm7
+        } else {
+            SmsEncoding::Ucs2
+        };
+
+        // The size of each character, which can't be split across segments.
+        let costs: Vec<usize> = match encoding {
+            SmsEncoding::Gsm7 => content.chars().filter_map(gsm7_septets).collect(),
+            SmsEncoding::Ucs2 => content.chars().map(char::len_utf16).collect(),
+        };
+        let units: usize = costs.iter().sum();
+
+        let segments = if units == 0 {
+            0
+        } else if units <= encoding.single_segment_units() {
+            1
+        } else {
+            count_segments(&costs, encoding.multi_segment_units())
+        };
+
+        Self {
+            encoding,
+            characters: costs.len(),
+            units,
+            segments,
+        }
+    }
+}
+
+/// Get the characters in the content that can't be encoded as GSM-7, and so force UCS-2.
+/// Each character is only returned once, in the order they first appear.
+#[must_use]
+pub fn non_gsm7_characters(content: &str) -> Vec<char> {
+    let mut characters = Vec::new();
+    for c in content.chars() {
+        if gsm7_septets(c).is_none() && !characters.contains(&c) {
+            characters.push(c);
+        }
+    }
+    characters
+}
+
+/// Get the number of septets a character takes in GSM-7, or None if it can't be encoded.
+fn gsm7_septets(c: char) -> Option<usize> {
+    if GSM7_BASIC.contains(c) {
+        Some(1)
+    } else if GSM7_EXTENSION.contains(c) {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Pack characters into segments of a maximum size, without splitting any character.
+fn count_segments(costs: &[usize], segment_units: usize) -> usize {
+    let mut segments = 1;
+    let mut used = 0;
+    for &cost in costs {
+        if used + cost > segment_units {
+            segments += 1;
+            used = 0;
+        }
+        used += cost;
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(content: &str) -> usize {
+        SmsEncodingInfo::analyze(content).segments
+    }
+
+    #[test]
+    fn empty_content_has_no_segments() {
+        assert_eq!(segments(""), 0);
+    }
+
+    #[test]
+    fn gsm7_segment_boundaries() {
+        assert_eq!(segments(&"a".repeat(160)), 1);
+        assert_eq!(segments(&"a".repeat(161)), 2);
+        assert_eq!(segments(&"a".repeat(306)), 2);
+        assert_eq!(segments(&"a".repeat(307)), 3);
+    }
+
+    #[test]
+    fn gsm7_extension_characters_cost_two_septets() {
+        let info = SmsEncodingInfo::analyze(&"€".repeat(80));
+        assert_eq!(info.encoding, SmsEncoding::Gsm7);
+        assert_eq!(info.characters, 80);
+        assert_eq!(info.units, 160);
+        assert_eq!(info.segments, 1);
+
+        assert_eq!(segments(&"€".repeat(81)), 2);
+    }
+
+    #[test]
+    fn gsm7_escape_sequences_are_not_split() {
+        let content = format!("{}€{}", "a".repeat(152), "a".repeat(152));
+        assert_eq!(SmsEncodingInfo::analyze(&content).units, 306);
+        assert_eq!(segments(&content), 3);
+    }
+
+    #[test]
+    fn ucs2_segment_boundaries() {
+        let info = SmsEncodingInfo::analyze(&"ł".repeat(70));
+        assert_eq!(info.encoding, SmsEncoding::Ucs2);
+        assert_eq!(info.segments, 1);
+
+        assert_eq!(segments(&"ł".repeat(71)), 2);
+        assert_eq!(segments(&"ł".repeat(134)), 2);
+        assert_eq!(segments(&"ł".repeat(135)), 3);
+    }
+
+    #[test]
+    fn ucs2_surrogate_pairs_are_not_split() {
+        let content = format!("{}👋{}", "ł".repeat(66), "ł".repeat(66));
+        assert_eq!(SmsEncodingInfo::analyze(&content).units, 134);
+        assert_eq!(segments(&content), 3);
+    }
+
+    #[test]
+    fn reports_each_non_gsm7_character_once() {
+        assert_eq!(non_gsm7_characters("héłło 👋 ł"), vec!['ł', '👋']);
+        assert!(non_gsm7_characters("Hello €").is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+//! `chrono` conversions for the raw timestamps used by `sms_types`.
+//! Message and report timestamps are UNIX seconds, GNSS times are NMEA `hhmmss.sss` strings.
+
+/// Convert a raw UNIX timestamp in seconds (eg: `created_at`, `completed_at`) to a UTC datetime.
+///
+/// # Example
+/// ```
+/// use sms_client::types::sms::SmsMessage;
+/// use sms_client::types::time::unix_to_utc;
+///
+/// fn received_at(message: &SmsMessage) {
+///     if let Some(created_at) = message.created_at.and_then(unix_to_utc) {
+///         println!("Received at {created_at}");
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn unix_to_utc(timestamp: u32) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::from_timestamp(i64::from(timestamp), 0)
+}
+
+/// Convert a UTC datetime back into a raw UNIX timestamp in seconds.
+/// Returns None if the datetime is outside the `u32` range (before 1970 or after 2106).
+#[must_use]
+pub fn utc_to_unix(datetime: chrono::DateTime<chrono::Utc>) -> Option<u32> {
+    u32::try_from(datetime.timestamp()).ok()
+}
+
+/// Parse a GNSS NMEA UTC time (`hhmmss` with optional fractional seconds, eg: `104258.000`).
+#[must_use]
+pub fn parse_gnss_utc_time(utc_time: &str) -> Option<chrono::NaiveTime> {
+    let utc_time = utc_time.trim();
+    let (whole, fraction) = utc_time.split_once('.').unwrap_or((utc_time, ""));
+    if whole.len() != 6 || !whole.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let hour = whole[0..2].parse().ok()?;
+    let minute = whole[2..4].parse().ok()?;
+    let second = whole[4..6].parse().ok()?;
+
+    // Pad or truncate the fraction to nanoseconds.
+    let nanos = if fraction.is_empty() {
+        0
+    } else {
+        let digits: String = fraction
+            .chars()
+            .chain(std::iter::repeat('0'))
+            .take(9)
+            .collect();
+        digits.parse().ok()?
+    };
+
+    chrono::NaiveTime::from_hms_nano_opt(hour, minute, second, nanos)
+}
+
+/// Combine a GNSS NMEA UTC time with a date into a UTC datetime.
+#[must_use]
+pub fn gnss_utc_datetime(
+    date: chrono::NaiveDate,
+    utc_time: &str,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    parse_gnss_utc_time(utc_time).map(|time| date.and_time(time).and_utc())
+}
@@ -0,0 +1,25 @@
+//! Deprecated 1.x type names, kept so applications can migrate to the current
+//! `sms_types` names incrementally. Each alias is the same type as its replacement,
+//! so values can be passed between old and new code without conversion.
+
+#![allow(deprecated)]
+
+/// Renamed to `sms::SmsOutgoingMessage`.
+#[deprecated(note = "Renamed to `types::sms::SmsOutgoingMessage`")]
+pub type HttpOutgoingSmsMessage = sms_types::sms::SmsOutgoingMessage;
+
+/// Renamed to `sms::SmsMessage`.
+#[deprecated(note = "Renamed to `types::sms::SmsMessage`")]
+pub type SmsStoredMessage = sms_types::sms::SmsMessage;
+
+/// Renamed to `sms::SmsDeliveryReport`.
+#[deprecated(note = "Renamed to `types::sms::SmsDeliveryReport`")]
+pub type HttpSmsDeliveryReport = sms_types::sms::SmsDeliveryReport;
+
+/// Renamed to `events::Event`.
+#[deprecated(note = "Renamed to `types::events::Event`")]
+pub type WebsocketMessage = sms_types::events::Event;
+
+/// Renamed to `gnss::PositionReport`.
+#[deprecated(note = "Renamed to `types::gnss::PositionReport`")]
+pub type GnssPositionReport = sms_types::gnss::PositionReport;
@@ -0,0 +1,247 @@
+//! Typed SMS validity period, converting to/from the GSM relative TP-VP encoding.
+
+/// The number of seconds in a minute, used for `Duration` conversions.
+const SECS_PER_MINUTE: u64 = 60;
+
+/// An error generated when a validity period cannot be represented by TP-VP.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidityPeriodError {
+    /// Minutes must be within 5 to 1440 (24 hours).
+    #[error("Validity period minutes must be within 5..=1440, got {0}")]
+    MinutesOutOfRange(u16),
+
+    /// Hours must be within 1 to 24.
+    #[error("Validity period hours must be within 1..=24, got {0}")]
+    HoursOutOfRange(u8),
+
+    /// Days must be within 1 to 30.
+    #[error("Validity period days must be within 1..=30, got {0}")]
+    DaysOutOfRange(u8),
+
+    /// Weeks must be within 1 to 63.
+    #[error("Validity period weeks must be within 1..=63, got {0}")]
+    WeeksOutOfRange(u8),
+
+    /// Duration is zero or longer than the maximum of 63 weeks.
+    #[error("Validity period duration must be within 5 minutes and 63 weeks")]
+    DurationOutOfRange,
+}
+
+/// How long the SMSC should attempt delivery before discarding a message.
+/// Values that fall between two encodable steps are rounded up to the next step.
+///
+/// # Example
+/// ```
+/// use sms_client::types::validity::ValidityPeriod;
+///
+/// assert_eq!(ValidityPeriod::Hours(24).to_tp_vp(), Ok(167));
+/// assert_eq!(ValidityPeriod::from_tp_vp(173), ValidityPeriod::Days(7));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidityPeriod {
+    /// 5 to 1440 minutes, in steps of 5 up to 12 hours then steps of 30.
+    Minutes(u16),
+
+    /// 1 to 24 hours.
+    Hours(u8),
+
+    /// 1 to 30 days.
+    Days(u8),
+
+    /// 1 to 63 weeks.
+    Weeks(u8),
+
+    /// An already encoded TP-VP value, used as-is.
+    Raw(u8),
+}
+impl ValidityPeriod {
+    /// Encode the validity period as a relative TP-VP octet.
+    pub fn to_tp_vp(self) -> Result<u8, ValidityPeriodError> {
+        match self {
+            Self::Minutes(minutes) => {
+                if !(5..=1440).contains(&minutes) {
+                    return Err(ValidityPeriodError::MinutesOutOfRange(minutes));
+                }
+                Ok(Self::encode_minutes(minutes))
+            }
+            Self::Hours(hours) => {
+                if !(1..=24).contains(&hours) {
+                    return Err(ValidityPeriodError::HoursOutOfRange(hours));
+                }
+                Ok(Self::encode_minutes(u16::from(hours) * 60))
+            }
+            Self::Days(days) => match days {
+                1 => Ok(Self::encode_minutes(1440)),
+                2..=30 => Ok(166 + days),
+                _ => Err(ValidityPeriodError::DaysOutOfRange(days)),
+            },
+            Self::Weeks(weeks) => match weeks {
+                1..=4 => Ok(166 + weeks * 7),
+                5..=63 => Ok(192 + weeks),
+                _ => Err(ValidityPeriodError::WeeksOutOfRange(weeks)),
+            },
+            Self::Raw(value) => Ok(value),
+        }
+    }
+
+    /// Decode a relative TP-VP octet into the most natural unit for its range.
+    #[must_use]
+    pub fn from_tp_vp(value: u8) -> Self {
+        match value {
+            0..=143 => Self::Minutes((u16::from(value) + 1) * 5),
+            144..=167 => Self::Minutes(720 + (u16::from(value) - 143) * 30),
+            168..=196 => Self::Days(value - 166),
+            197..=255 => Self::Weeks(value - 192),
+        }
+    }
+
+    /// Get the actual period the SMSC will apply, after rounding to an encodable step.
+    pub fn to_duration(self) -> Result<std::time::Duration, ValidityPeriodError> {
+        let minutes = match Self::from_tp_vp(self.to_tp_vp()?) {
+            Self::Minutes(minutes) => u64::from(minutes),
+            Self::Days(days) => u64::from(days) * 24 * 60,
+            Self::Weeks(weeks) => u64::from(weeks) * 7 * 24 * 60,
+            Self::Hours(_) | Self::Raw(_) => unreachable!("from_tp_vp never returns Hours or Raw"),
+        };
+        Ok(std::time::Duration::from_secs(minutes * SECS_PER_MINUTE))
+    }
+
+    /// Round minutes up to the next encodable relative step.
+    fn encode_minutes(minutes: u16) -> u8 {
+        let value = if minutes <= 720 {
+            minutes.div_ceil(5) - 1
+        } else {
+            143 + (minutes - 720).div_ceil(30)
+        };
+
+        // Minutes are validated to 5..=1440, so this is always within 0..=167.
+        u8::try_from(value).unwrap_or(167)
+    }
+}
+impl TryFrom<std::time::Duration> for ValidityPeriod {
+    type Error = ValidityPeriodError;
+
+    /// Select the shortest encodable validity period that is at least `duration`.
+    fn try_from(duration: std::time::Duration) -> Result<Self, Self::Error> {
+        let minutes = duration.as_secs().div_ceil(SECS_PER_MINUTE);
+        match minutes {
+            0 => Err(ValidityPeriodError::DurationOutOfRange),
+            1..=1440 => Ok(Self::Minutes(u16::try_from(minutes.max(5)).unwrap_or(1440))),
+            _ => {
+                let days = minutes.div_ceil(24 * 60);
+                if days <= 30 {
+                    return Ok(Self::Days(u8::try_from(days).unwrap_or(30)));
+                }
+
+                let weeks = days.div_ceil(7);
+                u8::try_from(weeks)
+                    .ok()
+                    .filter(|weeks| *weeks <= 63)
+                    .map(Self::Weeks)
+                    .ok_or(ValidityPeriodError::DurationOutOfRange)
+            }
+        }
+    }
+}
+impl From<u8> for ValidityPeriod {
+    fn from(value: u8) -> Self {
+        Self::Raw(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minutes_encode_at_step_boundaries() {
+        assert_eq!(ValidityPeriod::Minutes(5).to_tp_vp(), Ok(0));
+        assert_eq!(ValidityPeriod::Minutes(720).to_tp_vp(), Ok(143));
+        assert_eq!(ValidityPeriod::Minutes(721).to_tp_vp(), Ok(144));
+        assert_eq!(ValidityPeriod::Minutes(1440).to_tp_vp(), Ok(167));
+    }
+
+    #[test]
+    fn minutes_outside_range_are_rejected() {
+        assert_eq!(
+            ValidityPeriod::Minutes(4).to_tp_vp(),
+            Err(ValidityPeriodError::MinutesOutOfRange(4))
+        );
+        assert_eq!(
+            ValidityPeriod::Minutes(1441).to_tp_vp(),
+            Err(ValidityPeriodError::MinutesOutOfRange(1441))
+        );
+    }
+
+    #[test]
+    fn days_and_weeks_encode_at_range_boundaries() {
+        assert_eq!(ValidityPeriod::Days(1).to_tp_vp(), Ok(167));
+        assert_eq!(ValidityPeriod::Days(2).to_tp_vp(), Ok(168));
+        assert_eq!(ValidityPeriod::Days(30).to_tp_vp(), Ok(196));
+        assert_eq!(ValidityPeriod::Weeks(4).to_tp_vp(), Ok(194));
+        assert_eq!(ValidityPeriod::Weeks(5).to_tp_vp(), Ok(197));
+        assert_eq!(ValidityPeriod::Weeks(63).to_tp_vp(), Ok(255));
+
+        assert_eq!(
+            ValidityPeriod::Days(31).to_tp_vp(),
+            Err(ValidityPeriodError::DaysOutOfRange(31))
+        );
+        assert_eq!(
+            ValidityPeriod::Weeks(64).to_tp_vp(),
+            Err(ValidityPeriodError::WeeksOutOfRange(64))
+        );
+    }
+
+    #[test]
+    fn decodes_each_tp_vp_range() {
+        assert_eq!(ValidityPeriod::from_tp_vp(0), ValidityPeriod::Minutes(5));
+        assert_eq!(
+            ValidityPeriod::from_tp_vp(143),
+            ValidityPeriod::Minutes(720)
+        );
+        assert_eq!(
+            ValidityPeriod::from_tp_vp(144),
+            ValidityPeriod::Minutes(750)
+        );
+        assert_eq!(
+            ValidityPeriod::from_tp_vp(167),
+            ValidityPeriod::Minutes(1440)
+        );
+        assert_eq!(ValidityPeriod::from_tp_vp(168), ValidityPeriod::Days(2));
+        assert_eq!(ValidityPeriod::from_tp_vp(196), ValidityPeriod::Days(30));
+        assert_eq!(ValidityPeriod::from_tp_vp(197), ValidityPeriod::Weeks(5));
+        assert_eq!(ValidityPeriod::from_tp_vp(255), ValidityPeriod::Weeks(63));
+    }
+
+    #[test]
+    fn durations_round_up_to_the_next_step() {
+        assert_eq!(
+            ValidityPeriod::Minutes(7).to_duration(),
+            Ok(std::time::Duration::from_secs(10 * 60))
+        );
+        assert_eq!(
+            ValidityPeriod::try_from(std::time::Duration::from_secs(1)),
+            Ok(ValidityPeriod::Minutes(5))
+        );
+        assert_eq!(
+            ValidityPeriod::try_from(std::time::Duration::from_secs(1441 * 60)),
+            Ok(ValidityPeriod::Days(2))
+        );
+        assert_eq!(
+            ValidityPeriod::try_from(std::time::Duration::from_secs(63 * 7 * 24 * 60 * 60)),
+            Ok(ValidityPeriod::Weeks(63))
+        );
+    }
+
+    #[test]
+    fn durations_outside_range_are_rejected() {
+        assert_eq!(
+            ValidityPeriod::try_from(std::time::Duration::ZERO),
+            Err(ValidityPeriodError::DurationOutOfRange)
+        );
+        assert_eq!(
+            ValidityPeriod::try_from(std::time::Duration::from_secs(64 * 7 * 24 * 60 * 60)),
+            Err(ValidityPeriodError::DurationOutOfRange)
+        );
+    }
+}
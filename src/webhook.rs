@@ -0,0 +1,363 @@
+//! Webhook receiver, an alternative event source for when a persistent WebSocket is impractical.
+//! SMS-API webhook callbacks carry the same JSON events as WebSocket frames, so they are
+//! handled by the same callbacks, subscribers, streams and automation rules.
+//!
+//! # Example
+//! ```
+//! use sms_client::config::WebhookConfig;
+//! use sms_client::types::events::Event;
+//! use sms_client::webhook::WebhookReceiver;
+//!
+//! async fn listen() -> std::io::Result<()> {
+//!     let config = WebhookConfig::new("0.0.0.0:3100".parse().unwrap()).with_auth("secret");
+//!
+//!     let mut receiver = WebhookReceiver::new(config);
+//!     receiver.on_message(|event| {
+//!         if let Event::IncomingMessage(message) = event {
+//!             println!("Received: {message:?}");
+//!         }
+//!     });
+//!
+//!     receiver.start().await?;
+//!     tokio::signal::ctrl_c().await
+//! }
+//! ```
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
+
+/// The largest accepted request line or header line, in bytes.
+const MAX_LINE_LENGTH: u64 = 8 * 1024;
+
+/// The most headers accepted in a single request.
+const MAX_HEADERS: usize = 64;
+
+/// Receives SMS-API webhook callbacks over HTTP, dispatching each event to the handlers.
+pub struct WebhookReceiver {
+    config: crate::config::WebhookConfig,
+    callback: Option<crate::ws::MessageCallback>,
+    parse_error_callback: Option<crate::ws::ParseErrorCallback>,
+    unknown_event_callback: Option<crate::ws::UnknownEventCallback>,
+    subscribers: crate::ws::subscription::Subscribers,
+    counters: crate::counters::Counters,
+    local_addr: Option<std::net::SocketAddr>,
+    listener_handle: Option<tokio::task::JoinHandle<()>>,
+}
+impl WebhookReceiver {
+    /// Create a new webhook receiver. Nothing is bound until it is started.
+    #[must_use]
+    pub fn new(config: crate::config::WebhookConfig) -> Self {
+        Self {
+            config,
+            callback: None,
+            parse_error_callback: None,
+            unknown_event_callback: None,
+            subscribers: crate::ws::subscription::Subscribers::default(),
+            counters: crate::counters::Counters::default(),
+            local_addr: None,
+            listener_handle: None,
+        }
+    }
+
+    /// Share cumulative counters, which are updated from received events.
+    /// This must be called before starting the receiver.
+    pub fn set_counters(&mut self, counters: crate::counters::Counters) {
+        self.counters = counters;
+    }
+
+    /// Set the message callback handler.
+    /// This must be called before starting the receiver.
+    pub fn on_message<F>(&mut self, callback: F)
+    where
+        F: Fn(sms_types::events::Event) + Send + Sync + 'static,
+    {
+        self.callback = Some(std::sync::Arc::new(callback));
+    }
+
+    /// Set a callback for request bodies that can't be decoded into an event.
+    /// It receives the raw body and the decode error. This must be called before starting the receiver.
+    pub fn on_parse_error<F>(&mut self, callback: F)
+    where
+        F: Fn(&str, &serde_json::Error) + Send + Sync + 'static,
+    {
+        self.parse_error_callback = Some(std::sync::Arc::new(callback));
+    }
+
+    /// Set a callback for events with an unrecognised type, eg: new event types sent by a newer
+    /// server. These are only passed here and acknowledged, they never reach the event
+    /// callback. Known events that fail to decode still go to the parse error callback.
+    /// This must be called before starting the receiver.
+    pub fn on_unknown_event<F>(&mut self, callback: F)
+    where
+        F: Fn(crate::ws::UnknownEvent) + Send + Sync + 'static,
+    {
+        self.unknown_event_callback = Some(std::sync::Arc::new(callback));
+    }
+
+    /// Add an additional message callback alongside the `on_message` handler and any other
+    /// subscribers. This can be called while running. The callback is removed when the
+    /// returned handle is dropped.
+    pub fn subscribe<F>(&self, callback: F) -> crate::ws::SubscriptionHandle
+    where
+        F: Fn(sms_types::events::Event) + Send + Sync + 'static,
+    {
+        self.subscribers.subscribe(std::sync::Arc::new(callback))
+    }
+
+    /// Add an automation rule, evaluated against every received message.
+    /// The rule is removed when the returned handle is dropped or cancelled.
+    pub fn add_automation(
+        &self,
+        rule: crate::ws::automation::AutomationRule,
+    ) -> crate::ws::SubscriptionHandle {
+        self.subscribers.subscribe(rule.into_callback())
+    }
+
+    /// Get a stream of every received message, alongside any callbacks.
+    /// This can be called while running, and only receives messages from then on.
+    pub fn stream(&self) -> crate::ws::EventStream {
+        crate::ws::EventStream::new(&self.subscribers)
+    }
+
+    /// Bind the listener and accept callbacks in the background (spawns a listener task).
+    pub async fn start(&mut self) -> std::io::Result<()> {
+        if self.listener_handle.is_some() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                "Webhook receiver is already running",
+            ));
+        }
+
+        let listener = tokio::net::TcpListener::bind(self.config.bind).await?;
+        self.local_addr = Some(listener.local_addr()?);
+
+        let connections = std::sync::Arc::new(tokio::sync::Semaphore::new(
+            self.config.max_connections.max(1),
+        ));
+        let handler = std::sync::Arc::new(RequestHandler {
+            config: self.config.clone(),
+            callback: self.dispatch_callback(),
+            parse_error_callback: self.parse_error_callback.clone(),
+            unknown_event_callback: self.unknown_event_callback.clone(),
+            counters: self.counters.clone(),
+        });
+
+        self.listener_handle = Some(tokio::spawn(async move {
+            loop {
+                // Wait for a free connection slot before accepting, so excess clients
+                // queue in the listen backlog instead of each holding a task open.
+                let Ok(permit) = std::sync::Arc::clone(&connections).acquire_owned().await else {
+                    return;
+                };
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        log::warn!("Failed to accept webhook connection: {e}");
+                        continue;
+                    }
+                };
+
+                let handler = std::sync::Arc::clone(&handler);
+                tokio::spawn(async move {
+                    if let Err(e) = handler.handle(stream).await {
+                        log::debug!("Webhook connection error: {e}");
+                    }
+                    drop(permit);
+                });
+            }
+        }));
+        Ok(())
+    }
+
+    /// Stop accepting callbacks. Requests already being handled are allowed to finish.
+    pub fn stop(&mut self) {
+        if let Some(handle) = self.listener_handle.take() {
+            handle.abort();
+        }
+        self.local_addr = None;
+    }
+
+    /// Get the address the receiver is listening on, if it's running.
+    /// This is useful when binding to port 0 to pick a free port.
+    #[must_use]
+    pub fn local_addr(&self) -> Option<std::net::SocketAddr> {
+        self.local_addr
+    }
+
+    /// Check if the receiver is currently listening.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.listener_handle.is_some()
+    }
+
+    /// Build the request callback, calling every subscriber and then the `on_message` handler.
+    fn dispatch_callback(&self) -> crate::ws::MessageCallback {
+        let callback = self.callback.clone();
+        let subscribers = self.subscribers.clone();
+
+        std::sync::Arc::new(move |event| {
+            subscribers.dispatch(&event);
+            if let Some(callback) = &callback {
+                callback(event);
+            }
+        })
+    }
+}
+impl Drop for WebhookReceiver {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+impl std::fmt::Debug for WebhookReceiver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookReceiver")
+            .field("bind", &self.config.bind)
+            .field("path", &self.config.path)
+            .field("local_addr", &self.local_addr)
+            .finish()
+    }
+}
+
+/// Handles a single webhook request per connection.
+struct RequestHandler {
+    config: crate::config::WebhookConfig,
+    callback: crate::ws::MessageCallback,
+    parse_error_callback: Option<crate::ws::ParseErrorCallback>,
+    unknown_event_callback: Option<crate::ws::UnknownEventCallback>,
+    counters: crate::counters::Counters,
+}
+impl RequestHandler {
+    /// Read a request, dispatch its event and write the response.
+    async fn handle(&self, stream: tokio::net::TcpStream) -> std::io::Result<()> {
+        let mut reader = tokio::io::BufReader::new(stream);
+        let status = tokio::time::timeout(self.config.read_timeout, self.read_request(&mut reader))
+            .await
+            .map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "Webhook request was not received in time",
+                )
+            })??;
+
+        let response =
+            format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+        let mut stream = reader.into_inner();
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+
+    /// Read and handle a request, returning the response status line.
+    async fn read_request(
+        &self,
+        reader: &mut tokio::io::BufReader<tokio::net::TcpStream>,
+    ) -> std::io::Result<&'static str> {
+        let request_line = read_line(reader).await?;
+        let mut parts = request_line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Ok("400 Bad Request");
+        };
+
+        // Read headers, keeping only the ones needed to validate and read the body.
+        let mut content_length = None;
+        let mut authorization = None;
+        let mut header_count = 0;
+        loop {
+            let line = read_line(reader).await?;
+            if line.is_empty() {
+                break;
+            }
+            header_count += 1;
+            if header_count > MAX_HEADERS {
+                return Ok("431 Request Header Fields Too Large");
+            }
+            let Some((name, value)) = line.split_once(':') else {
+                return Ok("400 Bad Request");
+            };
+
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse::<usize>().ok();
+            } else if name.eq_ignore_ascii_case("authorization") {
+                authorization = Some(value.to_string());
+            }
+        }
+
+        let path = target.split('?').next().unwrap_or(target);
+        if path != self.config.path {
+            return Ok("404 Not Found");
+        }
+        if method != "POST" {
+            return Ok("405 Method Not Allowed");
+        }
+        if let Some(expected) = &self.config.authorization
+            && !authorization
+                .as_deref()
+                .is_some_and(|value| constant_time_eq(value.as_bytes(), expected.as_bytes()))
+        {
+            return Ok("401 Unauthorized");
+        }
+
+        let Some(content_length) = content_length else {
+            return Ok("411 Length Required");
+        };
+        if content_length > self.config.max_body_size {
+            return Ok("413 Payload Too Large");
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).await?;
+
+        match crate::json::from_slice::<sms_types::events::Event>(&body) {
+            Ok(event) => {
+                self.counters.record_event(&event);
+                (self.callback)(event);
+                Ok("204 No Content")
+            }
+            Err(e) => {
+                if let Some(callback) = &self.unknown_event_callback
+                    && let Some(event) = crate::ws::UnknownEvent::from_slice(&body)
+                {
+                    callback(event);
+                    return Ok("204 No Content");
+                }
+
+                let text = String::from_utf8_lossy(&body);
+                log::warn!("Invalid webhook event: {:?} -> {:#?}", text, e);
+                self.counters.record_parse_error(&e);
+                if let Some(callback) = &self.parse_error_callback {
+                    callback(&text, &e);
+                }
+                Ok("400 Bad Request")
+            }
+        }
+    }
+}
+
+/// Read a single CRLF-terminated line, without the line ending.
+async fn read_line(
+    reader: &mut tokio::io::BufReader<tokio::net::TcpStream>,
+) -> std::io::Result<String> {
+    let mut line = Vec::new();
+    let read = (&mut *reader)
+        .take(MAX_LINE_LENGTH)
+        .read_until(b'\n', &mut line)
+        .await?;
+    if read == 0 || !line.ends_with(b"\n") {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Webhook request line is missing or too long",
+        ));
+    }
+
+    let line = String::from_utf8(line)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Compare two values without exiting early on the first difference, so the time taken
+/// doesn't reveal how much of a secret matched. Only the length comparison is observable.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
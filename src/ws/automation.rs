@@ -0,0 +1,130 @@
+//! Automations that run async actions when an event condition has held for long enough,
+//! eg: notifying an admin after the WebSocket has been disconnected for five minutes.
+
+type AutomationCondition = dyn Fn(&sms_types::events::Event) -> Option<bool> + Send + Sync;
+type AutomationAction =
+    dyn Fn() -> std::pin::Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync;
+
+/// A rule that runs an action once its condition has been active for the hold duration.
+/// The condition is checked against each received event: `Some(true)` when the event starts
+/// the condition, `Some(false)` when it clears it, and None for unrelated events.
+#[derive(Clone)]
+pub struct AutomationRule {
+    condition: std::sync::Arc<AutomationCondition>,
+    action: std::sync::Arc<AutomationAction>,
+    hold: std::time::Duration,
+    cooldown: std::time::Duration,
+}
+impl AutomationRule {
+    /// Create a rule that runs the action as soon as the condition becomes active.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use sms_client::types::events::Event;
+    /// use sms_client::ws::automation::AutomationRule;
+    ///
+    /// let rule = AutomationRule::new(
+    ///     |event| match event {
+    ///         Event::WebsocketConnectionUpdate { connected, .. } => Some(!connected),
+    ///         _ => None,
+    ///     },
+    ///     || async { log::error!("Gateway has been disconnected for 5 minutes!") },
+    /// )
+    /// .with_hold(Duration::from_secs(300))
+    /// .with_cooldown(Duration::from_secs(3600));
+    /// ```
+    pub fn new<C, A, Fut>(condition: C, action: A) -> Self
+    where
+        C: Fn(&sms_types::events::Event) -> Option<bool> + Send + Sync + 'static,
+        A: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self {
+            condition: std::sync::Arc::new(condition),
+            action: std::sync::Arc::new(move || Box::pin(action())),
+            hold: std::time::Duration::ZERO,
+            cooldown: std::time::Duration::ZERO,
+        }
+    }
+
+    /// Set how long the condition must stay active before the action runs.
+    #[must_use]
+    pub fn with_hold(mut self, hold: std::time::Duration) -> Self {
+        self.hold = hold;
+        self
+    }
+
+    /// Set the minimum time between action runs.
+    #[must_use]
+    pub fn with_cooldown(mut self, cooldown: std::time::Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Build the subscriber callback that evaluates this rule.
+    pub(crate) fn into_callback(self) -> crate::ws::MessageCallback {
+        let state = std::sync::Arc::new(std::sync::Mutex::new(RuleState::default()));
+
+        std::sync::Arc::new(move |event| {
+            let Some(active) = (self.condition)(&event) else {
+                return;
+            };
+
+            let mut guard = lock(&state);
+            if !active {
+                guard.active = None;
+                return;
+            }
+            if guard.active.is_some() {
+                return;
+            }
+
+            // Start a hold timer, which is cancelled if the condition clears (changing generation).
+            guard.generation = guard.generation.wrapping_add(1);
+            let generation = guard.generation;
+            guard.active = Some(generation);
+            drop(guard);
+
+            let state = std::sync::Arc::clone(&state);
+            let action = std::sync::Arc::clone(&self.action);
+            let (hold, cooldown) = (self.hold, self.cooldown);
+            tokio::spawn(async move {
+                tokio::time::sleep(hold).await;
+                {
+                    let mut guard = lock(&state);
+                    if guard.active != Some(generation)
+                        || guard.last_run.is_some_and(|last| last.elapsed() < cooldown)
+                    {
+                        return;
+                    }
+                    guard.last_run = Some(std::time::Instant::now());
+                }
+
+                action().await;
+            });
+        })
+    }
+}
+impl std::fmt::Debug for AutomationRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AutomationRule")
+            .field("hold", &self.hold)
+            .field("cooldown", &self.cooldown)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Default)]
+struct RuleState {
+    generation: u64,
+    active: Option<u64>,
+    last_run: Option<std::time::Instant>,
+}
+
+fn lock(state: &std::sync::Mutex<RuleState>) -> std::sync::MutexGuard<'_, RuleState> {
+    match state.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
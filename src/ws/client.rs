@@ -8,9 +8,18 @@ pub struct WebSocketClient {
     config: crate::config::WebSocketConfig,
     tls_config: Option<crate::config::TLSConfig>,
     callback: Option<crate::ws::MessageCallback>,
+    parse_error_callback: Option<crate::ws::ParseErrorCallback>,
+    unknown_event_callback: Option<crate::ws::UnknownEventCallback>,
+    subscribers: crate::ws::subscription::Subscribers,
     control_tx: Option<tokio::sync::mpsc::UnboundedSender<ControlMessage>>,
     worker_handle: Option<tokio::task::JoinHandle<WebsocketResult<()>>>,
     is_connected: std::sync::Arc<tokio::sync::RwLock<bool>>,
+    state: crate::state::StateCache,
+    counters: crate::counters::Counters,
+    debug_dump: Option<crate::dump::DebugDump>,
+    handlers: std::sync::Arc<InFlightHandlers>,
+    async_dispatcher: Option<tokio::task::JoinHandle<()>>,
+    connection_state: std::sync::Arc<tokio::sync::watch::Sender<crate::ws::ConnectionState>>,
 }
 impl WebSocketClient {
     /// Create a new WebSocket client.
@@ -22,18 +31,145 @@ impl WebSocketClient {
             config,
             tls_config,
             callback: None,
+            parse_error_callback: None,
+            unknown_event_callback: None,
+            subscribers: crate::ws::subscription::Subscribers::default(),
             control_tx: None,
             worker_handle: None,
             is_connected: std::sync::Arc::new(tokio::sync::RwLock::new(false)),
+            state: crate::state::StateCache::default(),
+            counters: crate::counters::Counters::default(),
+            debug_dump: None,
+            handlers: std::sync::Arc::default(),
+            async_dispatcher: None,
+            connection_state: std::sync::Arc::new(tokio::sync::watch::Sender::new(
+                crate::ws::ConnectionState::Stopped {
+                    reason: crate::ws::StopReason::NotStarted,
+                },
+            )),
         }
     }
 
+    /// Share a last-known state cache, which is updated from received events.
+    /// This must be called before starting the WebSocket connection.
+    pub fn set_state_cache(&mut self, state: crate::state::StateCache) {
+        self.state = state;
+    }
+
+    /// Share cumulative counters, which are updated from received events and reconnects.
+    /// This must be called before starting the WebSocket connection.
+    pub fn set_counters(&mut self, counters: crate::counters::Counters) {
+        self.counters = counters;
+    }
+
+    /// Record the handshake and every sent and received frame to a debug dump.
+    /// This must be called before starting the WebSocket connection.
+    pub fn set_debug_dump(&mut self, dump: crate::dump::DebugDump) {
+        self.debug_dump = Some(dump);
+    }
+
     /// Set the message callback handler.
     pub fn on_message<F>(&mut self, callback: F)
     where
         F: Fn(sms_types::events::Event) + Send + Sync + 'static,
     {
         self.callback = Some(std::sync::Arc::new(callback));
+        self.async_dispatcher = None;
+    }
+
+    /// Set a callback for messages that can't be decoded into an event, eg: after a server
+    /// update changes the event schema. It receives the raw text and the decode error.
+    /// This must be called before starting the WebSocket connection.
+    pub fn on_parse_error<F>(&mut self, callback: F)
+    where
+        F: Fn(&str, &serde_json::Error) + Send + Sync + 'static,
+    {
+        self.parse_error_callback = Some(std::sync::Arc::new(callback));
+    }
+
+    /// Set a callback for events with an unrecognised type, eg: new event types sent by a newer
+    /// server. These are passed here with their type name and raw data, instead of to the
+    /// parse error callback, and never reach the message callback. Known events that fail to
+    /// decode still go to the parse error callback. This must be called before starting the
+    /// WebSocket connection.
+    pub fn on_unknown_event<F>(&mut self, callback: F)
+    where
+        F: Fn(crate::ws::UnknownEvent) + Send + Sync + 'static,
+    {
+        self.unknown_event_callback = Some(std::sync::Arc::new(callback));
+    }
+
+    /// Set an async message callback handler. Each event is handled in its own task, with at
+    /// most `max_concurrent` handlers running at once. Events wait in order for a free slot.
+    /// This must be called from within a Tokio runtime. The handler is removed by `shutdown`.
+    pub fn on_message_async<F, Fut>(&mut self, max_concurrent: usize, callback: F)
+    where
+        F: Fn(sms_types::events::Event) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel();
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(max_concurrent.max(1)));
+        let handlers = std::sync::Arc::clone(&self.handlers);
+
+        // The dispatcher stops once the callback (and its sender) is replaced or dropped.
+        self.async_dispatcher = Some(tokio::spawn(async move {
+            while let Some((event, in_flight)) = event_rx.recv().await {
+                let Ok(permit) = std::sync::Arc::clone(&semaphore).acquire_owned().await else {
+                    break;
+                };
+
+                let handler = callback(event);
+                tokio::spawn(async move {
+                    handler.await;
+                    drop(permit);
+                    drop(in_flight);
+                });
+            }
+        }));
+
+        // Events are counted from when they are queued, so shutdown waits for queued events too.
+        self.callback = Some(std::sync::Arc::new(move |event| {
+            let in_flight = InFlightHandlers::start(&handlers);
+            let _ = event_tx.send((event, in_flight));
+        }));
+    }
+
+    /// Add an additional message callback alongside the `on_message` handler and any other
+    /// subscribers. Unlike `on_message` this never replaces an existing callback, and it can
+    /// be called while connected. The callback is removed when the returned handle is dropped.
+    pub fn subscribe<F>(&self, callback: F) -> crate::ws::SubscriptionHandle
+    where
+        F: Fn(sms_types::events::Event) + Send + Sync + 'static,
+    {
+        self.subscribers.subscribe(std::sync::Arc::new(callback))
+    }
+
+    /// Add an automation rule, evaluated against every received message.
+    /// The rule is removed when the returned handle is dropped or cancelled.
+    pub fn add_automation(
+        &self,
+        rule: crate::ws::automation::AutomationRule,
+    ) -> crate::ws::SubscriptionHandle {
+        self.subscribers.subscribe(rule.into_callback())
+    }
+
+    /// Get a stream of every received message, alongside any callbacks.
+    /// This can be called while connected, and only receives messages from then on.
+    pub fn stream(&self) -> crate::ws::EventStream {
+        crate::ws::EventStream::new(&self.subscribers)
+    }
+
+    /// Build the worker callback, calling every subscriber and then the `on_message` handler.
+    fn dispatch_callback(&self) -> crate::ws::MessageCallback {
+        let callback = self.callback.clone();
+        let subscribers = self.subscribers.clone();
+
+        std::sync::Arc::new(move |event| {
+            subscribers.dispatch(&event);
+            if let Some(callback) = &callback {
+                callback(event);
+            }
+        })
     }
 
     /// Start the WebSocket connection in the background (spawns a worker task).
@@ -48,8 +184,14 @@ impl WebSocketClient {
         let worker_loop = WorkerLoop::new(
             self.config.clone(),
             self.tls_config.clone(),
-            self.callback.clone(),
+            Some(self.dispatch_callback()),
+            self.parse_error_callback.clone(),
+            self.unknown_event_callback.clone(),
             std::sync::Arc::clone(&self.is_connected),
+            self.state.clone(),
+            self.counters.clone(),
+            self.debug_dump.clone(),
+            std::sync::Arc::clone(&self.connection_state),
         );
 
         let worker_handle = tokio::spawn(async move { worker_loop.run(control_rx).await });
@@ -66,8 +208,14 @@ impl WebSocketClient {
         let worker_loop = WorkerLoop::new(
             self.config.clone(),
             self.tls_config.clone(),
-            self.callback.clone(),
+            Some(self.dispatch_callback()),
+            self.parse_error_callback.clone(),
+            self.unknown_event_callback.clone(),
             std::sync::Arc::clone(&self.is_connected),
+            self.state.clone(),
+            self.counters.clone(),
+            self.debug_dump.clone(),
+            std::sync::Arc::clone(&self.connection_state),
         );
 
         // Run directly in this task (no spawn)
@@ -91,6 +239,54 @@ impl WebSocketClient {
         Ok(())
     }
 
+    /// Replace the filtered listen events (None for all events). If the worker is running,
+    /// it reconnects with the new filters in the connection query-string.
+    pub fn set_event_filters(&mut self, events: Option<Vec<String>>) -> WebsocketResult<()> {
+        self.config.filtered_events.clone_from(&events);
+        if let Some(tx) = &self.control_tx {
+            tx.send(ControlMessage::SetFilters(events))
+                .map_err(|_| WebsocketError::ChannelError)?;
+        }
+        Ok(())
+    }
+
+    /// Stop the WebSocket connection, then wait for queued events and running async handlers
+    /// to finish. No new messages are received once the connection is stopped. Returns true
+    /// if everything finished within `grace`, or false if the grace period elapsed first.
+    pub async fn shutdown(&mut self, grace: std::time::Duration) -> WebsocketResult<bool> {
+        let deadline = tokio::time::Instant::now() + grace;
+        if let Some(tx) = &self.control_tx {
+            let _ = tx.send(ControlMessage::Stop);
+        }
+
+        // The worker finishes its queued events before exiting.
+        let worker_finished = match self.worker_handle.take() {
+            Some(handle) => tokio::time::timeout_at(deadline, handle).await.is_ok(),
+            None => true,
+        };
+
+        self.control_tx = None;
+        *self.is_connected.write().await = false;
+
+        // Drop the async handler sender, so its dispatcher exits once the queue is drained.
+        self.callback = None;
+        let dispatcher_finished = match self.async_dispatcher.take() {
+            Some(handle) => tokio::time::timeout_at(deadline, handle).await.is_ok(),
+            None => true,
+        };
+
+        let handlers_finished = tokio::time::timeout_at(deadline, self.handlers.wait_idle())
+            .await
+            .is_ok();
+
+        Ok(worker_finished && dispatcher_finished && handlers_finished)
+    }
+
+    /// Get a receiver for the connection state, which can be awaited for state changes.
+    pub fn connection_state(&self) -> tokio::sync::watch::Receiver<crate::ws::ConnectionState> {
+        self.connection_state.subscribe()
+    }
+
     /// Check if the WebSocket is currently connected.
     pub async fn is_connected(&self) -> bool {
         *self.is_connected.read().await
@@ -107,6 +303,50 @@ impl WebSocketClient {
         }
     }
 }
+/// Counts queued and running async message handlers, so shutdown can wait for them.
+#[derive(Default)]
+struct InFlightHandlers {
+    count: std::sync::atomic::AtomicUsize,
+    idle: tokio::sync::Notify,
+}
+impl InFlightHandlers {
+    /// Count a handler as pending until the returned guard is dropped.
+    fn start(handlers: &std::sync::Arc<Self>) -> InFlightGuard {
+        handlers
+            .count
+            .fetch_add(1, std::sync::atomic::Ordering::AcqRel);
+        InFlightGuard(std::sync::Arc::clone(handlers))
+    }
+
+    /// Wait until no handlers are running.
+    async fn wait_idle(&self) {
+        loop {
+            let notified = self.idle.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if self.count.load(std::sync::atomic::Ordering::Acquire) == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+struct InFlightGuard(std::sync::Arc<InFlightHandlers>);
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self
+            .0
+            .count
+            .fetch_sub(1, std::sync::atomic::Ordering::AcqRel)
+            == 1
+        {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
 impl Drop for WebSocketClient {
     fn drop(&mut self) {
         // Send stop signal to worker if still running.
@@ -8,6 +8,7 @@ pub struct ConnectionParams {
     pub url: String,
     pub authorization: Option<http::HeaderValue>,
     pub connector: Option<tokio_tungstenite::Connector>,
+    pub protocol_config: Option<tungstenite::protocol::WebSocketConfig>,
 }
 impl ConnectionParams {
     /// Create connection parameters from configuration
@@ -39,12 +40,14 @@ impl ConnectionParams {
             url: url.to_string(),
             authorization,
             connector,
+            protocol_config: config.protocol_config,
         })
     }
 
-    /// Establish WebSocket connection
+    /// Establish WebSocket connection, recording the handshake to the debug dump if set.
     pub async fn connect(
         &self,
+        debug_dump: Option<&crate::dump::DebugDump>,
     ) -> WebsocketResult<
         tokio_tungstenite::WebSocketStream<
             tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
@@ -57,6 +60,9 @@ impl ConnectionParams {
         if let Some(token) = &self.authorization {
             request.headers_mut().append("authorization", token.clone());
         }
+        if let Some(dump) = debug_dump {
+            dump.record_ws_handshake_request(&self.url, request.headers());
+        }
 
         // Connect with optional TLS support
         let ws_stream = match &self.connector {
@@ -64,14 +70,22 @@ impl ConnectionParams {
             Some(connector) => {
                 match tokio_tungstenite::connect_async_tls_with_config(
                     request,
-                    None,
+                    self.protocol_config,
                     false,
                     Some(connector.clone()),
                 )
                 .await
                 {
-                    Ok((stream, _)) => stream,
-                    Err(e) => return Self::handle_connection_error(e),
+                    Ok((stream, response)) => {
+                        if let Some(dump) = debug_dump {
+                            dump.record_ws_handshake_response(
+                                response.status(),
+                                response.headers(),
+                            );
+                        }
+                        stream
+                    }
+                    Err(e) => return Self::handle_connection_error(e, debug_dump),
                 }
             }
 
@@ -82,9 +96,20 @@ impl ConnectionParams {
                 ));
             }
 
-            None => match tokio_tungstenite::connect_async(request).await {
-                Ok((stream, _)) => stream,
-                Err(e) => return Self::handle_connection_error(e),
+            None => match tokio_tungstenite::connect_async_with_config(
+                request,
+                self.protocol_config,
+                false,
+            )
+            .await
+            {
+                Ok((stream, response)) => {
+                    if let Some(dump) = debug_dump {
+                        dump.record_ws_handshake_response(response.status(), response.headers());
+                    }
+                    stream
+                }
+                Err(e) => return Self::handle_connection_error(e, debug_dump),
             },
         };
 
@@ -95,12 +120,16 @@ impl ConnectionParams {
     /// Handle connection errors, checking for authorization failures
     fn handle_connection_error(
         error: tungstenite::Error,
+        debug_dump: Option<&crate::dump::DebugDump>,
     ) -> WebsocketResult<
         tokio_tungstenite::WebSocketStream<
             tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
         >,
     > {
         if let tungstenite::Error::Http(response) = &error {
+            if let Some(dump) = debug_dump {
+                dump.record_ws_handshake_response(response.status(), response.headers());
+            }
             if response.status() == http::StatusCode::UNAUTHORIZED {
                 return Err(WebsocketError::Unauthorized);
             }
@@ -1,14 +1,138 @@
 //! WebSocket client for receiving real-time SMS messages.
 
+pub mod automation;
 pub mod error;
 
 mod client;
 mod connection;
+mod queue;
+pub(crate) mod subscription;
 mod tls;
 mod worker;
 
 pub use client::WebSocketClient;
 pub use error::{WebsocketError, WebsocketResult};
+pub use subscription::{EventStream, SubscriptionHandle};
 
 /// A callback to be run when the websocket receives a message.
 pub type MessageCallback = std::sync::Arc<dyn Fn(sms_types::events::Event) + Send + Sync>;
+
+/// A callback to be run with the raw text and error when a websocket message can't be decoded.
+pub type ParseErrorCallback = std::sync::Arc<dyn Fn(&str, &serde_json::Error) + Send + Sync>;
+
+/// A callback to be run with events whose `type` isn't recognised.
+pub type UnknownEventCallback = std::sync::Arc<dyn Fn(UnknownEvent) + Send + Sync>;
+
+/// An event with a `type` the client doesn't recognise, eg: a new event type sent by a newer
+/// SMS-API server. These are only passed to the unknown event callback, they don't reach
+/// `on_message` handlers, subscribers, streams or automation rules, as those receive the
+/// typed `Event` which has no variant for them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnknownEvent {
+    /// The event `type` name.
+    pub event_type: String,
+
+    /// The raw event `data`, or null if there was none.
+    pub data: serde_json::Value,
+}
+impl UnknownEvent {
+    /// Read the event type and data from a message that couldn't be decoded into an event.
+    /// Returns None if the message isn't an object with a string `type` field, or if that
+    /// type is a known event (so it has a malformed payload, which is a parse error instead).
+    pub(crate) fn from_slice(raw: &[u8]) -> Option<Self> {
+        let serde_json::Value::Object(mut object) = serde_json::from_slice(raw).ok()? else {
+            return None;
+        };
+        let serde_json::Value::String(event_type) = object.remove("type")? else {
+            return None;
+        };
+        if is_known_event_type(&event_type) {
+            return None;
+        }
+
+        Some(Self {
+            event_type,
+            data: object.remove("data").unwrap_or_default(),
+        })
+    }
+}
+
+/// Check if an event `type` tag is one of the `Event` variants. This decodes only the tag
+/// with serde, so the known variant list always matches the `Event` type itself.
+fn is_known_event_type(event_type: &str) -> bool {
+    use serde::Deserialize;
+
+    /// Separates an unrecognised tag from any other decode failure, eg: missing data.
+    #[derive(Debug)]
+    enum TagProbeError {
+        UnknownVariant,
+        Other,
+    }
+    impl std::fmt::Display for TagProbeError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(match self {
+                Self::UnknownVariant => "unknown variant",
+                Self::Other => "invalid event",
+            })
+        }
+    }
+    impl std::error::Error for TagProbeError {}
+    impl serde::de::Error for TagProbeError {
+        fn custom<T: std::fmt::Display>(_msg: T) -> Self {
+            Self::Other
+        }
+
+        fn unknown_variant(_variant: &str, _expected: &'static [&'static str]) -> Self {
+            Self::UnknownVariant
+        }
+    }
+
+    let tag = serde::de::value::MapDeserializer::<_, TagProbeError>::new(std::iter::once((
+        "type", event_type,
+    )));
+    !matches!(
+        sms_types::events::Event::deserialize(tag),
+        Err(TagProbeError::UnknownVariant)
+    )
+}
+
+/// The WebSocket worker connection state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Making the first connection attempt.
+    Connecting,
+
+    /// Connected and receiving events.
+    Connected,
+
+    /// Disconnected, and waiting to make another connection attempt.
+    Reconnecting {
+        /// The number of reconnection attempts made so far, including the next one.
+        attempt: u32,
+    },
+
+    /// The worker isn't running.
+    Stopped {
+        /// Why the worker stopped.
+        reason: StopReason,
+    },
+}
+
+/// Why the WebSocket worker stopped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    /// The worker hasn't been started.
+    NotStarted,
+
+    /// A stop was requested by the client.
+    Requested,
+
+    /// The connection closed and auto-reconnect is disabled.
+    Disconnected,
+
+    /// The server rejected the authorization token.
+    Unauthorized,
+
+    /// The worker stopped because of an error.
+    Error(String),
+}
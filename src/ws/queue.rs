@@ -0,0 +1,193 @@
+//! Bounded queue between the socket reader and message callbacks, so a slow
+//! callback can't stall the connection (and its ping/pong) while events are handled.
+
+use crate::config::EventQueueOverflow;
+use sms_types::events::Event;
+
+/// A single-producer, single-consumer bounded event queue with an overflow policy.
+pub(crate) struct EventQueue {
+    events: std::sync::Mutex<std::collections::VecDeque<Event>>,
+    capacity: usize,
+    overflow: EventQueueOverflow,
+    closed: std::sync::atomic::AtomicBool,
+    items_available: tokio::sync::Notify,
+    space_available: tokio::sync::Notify,
+}
+impl EventQueue {
+    /// Create an empty queue. A zero capacity is treated as one.
+    pub(crate) fn new(config: crate::config::EventQueueConfig) -> Self {
+        Self {
+            events: std::sync::Mutex::new(std::collections::VecDeque::new()),
+            capacity: config.capacity.max(1),
+            overflow: config.overflow,
+            closed: std::sync::atomic::AtomicBool::new(false),
+            items_available: tokio::sync::Notify::new(),
+            space_available: tokio::sync::Notify::new(),
+        }
+    }
+
+    /// Add an event, applying the overflow policy if the queue is full.
+    pub(crate) async fn push(&self, event: Event) {
+        loop {
+            {
+                let mut events = self.lock();
+                if events.len() < self.capacity {
+                    events.push_back(event);
+                    self.items_available.notify_one();
+                    return;
+                }
+
+                match self.overflow {
+                    EventQueueOverflow::Block => {}
+                    EventQueueOverflow::DropOldest => {
+                        log::warn!("WebSocket event queue is full, dropping the oldest event");
+                        events.pop_front();
+                        events.push_back(event);
+                        self.items_available.notify_one();
+                        return;
+                    }
+                    EventQueueOverflow::DropNewest => {
+                        log::warn!("WebSocket event queue is full, dropping the newest event");
+                        return;
+                    }
+                }
+            }
+
+            self.space_available.notified().await;
+        }
+    }
+
+    /// Take the next event, waiting until one is available.
+    /// Returns None once the queue is closed and every queued event has been taken.
+    pub(crate) async fn pop(&self) -> Option<Event> {
+        loop {
+            if let Some(event) = self.lock().pop_front() {
+                self.space_available.notify_one();
+                return Some(event);
+            }
+            if self.closed.load(std::sync::atomic::Ordering::Acquire) {
+                return None;
+            }
+
+            self.items_available.notified().await;
+        }
+    }
+
+    /// Stop accepting new events, letting the consumer finish the queued ones.
+    pub(crate) fn close(&self) {
+        self.closed
+            .store(true, std::sync::atomic::Ordering::Release);
+        self.items_available.notify_one();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, std::collections::VecDeque<Event>> {
+        match self.events.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Closes the queue when dropped, so the dispatcher exits however the worker loop ends.
+pub(crate) struct CloseOnDrop(pub(crate) std::sync::Arc<EventQueue>);
+impl Drop for CloseOnDrop {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+/// Call the callback for each queued event until the queue is closed and empty.
+pub(crate) async fn run_dispatcher(
+    queue: std::sync::Arc<EventQueue>,
+    callback: crate::ws::MessageCallback,
+) {
+    while let Some(event) = queue.pop().await {
+        callback(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(capacity: usize, overflow: EventQueueOverflow) -> std::sync::Arc<EventQueue> {
+        std::sync::Arc::new(EventQueue::new(crate::config::EventQueueConfig {
+            capacity,
+            overflow,
+        }))
+    }
+
+    /// A distinguishable event, tagged by its two flags.
+    fn event(connected: bool, reconnect: bool) -> Event {
+        Event::WebsocketConnectionUpdate {
+            connected,
+            reconnect,
+        }
+    }
+
+    fn flags(event: Option<Event>) -> Option<(bool, bool)> {
+        match event? {
+            Event::WebsocketConnectionUpdate {
+                connected,
+                reconnect,
+            } => Some((connected, reconnect)),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_keeps_the_newest_events() {
+        let queue = queue(2, EventQueueOverflow::DropOldest);
+        queue.push(event(false, false)).await;
+        queue.push(event(false, true)).await;
+        queue.push(event(true, false)).await;
+        queue.close();
+
+        assert_eq!(flags(queue.pop().await), Some((false, true)));
+        assert_eq!(flags(queue.pop().await), Some((true, false)));
+        assert!(queue.pop().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn drop_newest_keeps_the_oldest_events() {
+        let queue = queue(2, EventQueueOverflow::DropNewest);
+        queue.push(event(false, false)).await;
+        queue.push(event(false, true)).await;
+        queue.push(event(true, false)).await;
+        queue.close();
+
+        assert_eq!(flags(queue.pop().await), Some((false, false)));
+        assert_eq!(flags(queue.pop().await), Some((false, true)));
+        assert!(queue.pop().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn block_waits_for_space() {
+        let queue = queue(1, EventQueueOverflow::Block);
+        queue.push(event(false, false)).await;
+
+        let producer = tokio::spawn({
+            let queue = std::sync::Arc::clone(&queue);
+            async move { queue.push(event(true, true)).await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!producer.is_finished());
+
+        assert_eq!(flags(queue.pop().await), Some((false, false)));
+        tokio::time::timeout(std::time::Duration::from_secs(1), producer)
+            .await
+            .expect("the blocked push should complete once there is space")
+            .expect("the producer task should not panic");
+        assert_eq!(flags(queue.pop().await), Some((true, true)));
+    }
+
+    #[tokio::test]
+    async fn zero_capacity_is_treated_as_one() {
+        let queue = queue(0, EventQueueOverflow::DropNewest);
+        queue.push(event(true, true)).await;
+        queue.close();
+
+        assert_eq!(flags(queue.pop().await), Some((true, true)));
+        assert!(queue.pop().await.is_none());
+    }
+}
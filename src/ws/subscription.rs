@@ -0,0 +1,131 @@
+//! Multiple independent WebSocket message subscribers, each removable by its handle.
+
+/// The shared set of subscribed callbacks.
+#[derive(Clone, Default)]
+pub(crate) struct Subscribers {
+    inner: std::sync::Arc<std::sync::RwLock<SubscriberList>>,
+}
+
+#[derive(Default)]
+struct SubscriberList {
+    next_id: u64,
+    callbacks: Vec<(u64, crate::ws::MessageCallback)>,
+}
+
+impl Subscribers {
+    /// Add a callback, returning a handle that removes it when dropped.
+    pub(crate) fn subscribe(&self, callback: crate::ws::MessageCallback) -> SubscriptionHandle {
+        let mut list = match self.inner.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let id = list.next_id;
+        list.next_id += 1;
+        list.callbacks.push((id, callback));
+
+        SubscriptionHandle {
+            subscribers: std::sync::Arc::downgrade(&self.inner),
+            id,
+            detached: false,
+        }
+    }
+
+    /// Call every subscribed callback with an event.
+    pub(crate) fn dispatch(&self, event: &sms_types::events::Event) {
+        // Clone the callbacks out so a callback can subscribe or unsubscribe without deadlocking.
+        let callbacks: Vec<crate::ws::MessageCallback> = match self.inner.read() {
+            Ok(guard) => guard.callbacks.iter().map(|(_, cb)| cb.clone()).collect(),
+            Err(poisoned) => poisoned
+                .into_inner()
+                .callbacks
+                .iter()
+                .map(|(_, cb)| cb.clone())
+                .collect(),
+        };
+
+        for callback in callbacks {
+            callback(event.clone());
+        }
+    }
+}
+
+/// A handle to a subscribed message callback. The callback is removed when the handle is
+/// dropped or cancelled, use `detach` to keep it for the lifetime of the client instead.
+#[must_use = "the subscription is removed when the handle is dropped"]
+pub struct SubscriptionHandle {
+    subscribers: std::sync::Weak<std::sync::RwLock<SubscriberList>>,
+    id: u64,
+    detached: bool,
+}
+impl SubscriptionHandle {
+    /// Remove the callback, so it receives no further messages.
+    pub fn cancel(self) {
+        drop(self);
+    }
+
+    /// Keep the callback subscribed for the lifetime of the client, dropping the handle.
+    pub fn detach(mut self) {
+        self.detached = true;
+    }
+}
+impl Drop for SubscriptionHandle {
+    fn drop(&mut self) {
+        if self.detached {
+            return;
+        }
+
+        if let Some(subscribers) = self.subscribers.upgrade() {
+            let mut list = match subscribers.write() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            list.callbacks.retain(|(id, _)| *id != self.id);
+        }
+    }
+}
+impl std::fmt::Debug for SubscriptionHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubscriptionHandle")
+            .field("id", &self.id)
+            .field("detached", &self.detached)
+            .finish()
+    }
+}
+
+/// A stream of WebSocket messages, as an alternative to registering a callback.
+/// Messages are buffered until read, and the subscription is removed when the stream is dropped.
+pub struct EventStream {
+    receiver: tokio::sync::mpsc::UnboundedReceiver<sms_types::events::Event>,
+    _handle: SubscriptionHandle,
+}
+impl EventStream {
+    pub(crate) fn new(subscribers: &Subscribers) -> Self {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let handle = subscribers.subscribe(std::sync::Arc::new(move |event| {
+            let _ = sender.send(event);
+        }));
+
+        Self {
+            receiver,
+            _handle: handle,
+        }
+    }
+}
+impl futures_util::Stream for EventStream {
+    type Item = sms_types::events::Event;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+impl std::fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventStream")
+            .field("buffered", &self.receiver.len())
+            .finish()
+    }
+}
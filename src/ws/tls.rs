@@ -24,26 +24,106 @@ pub fn create_connector(
         let Some(tls_config) = tls_config else {
             return Ok(None);
         };
-        let certificate_data = std::fs::read(&tls_config.certificate).map_err(|e| {
-            WebsocketError::TLSError(format!("Failed to read certificate file: {}", e))
-        })?;
 
-        // Determine certificate format based on file extension and content
-        let cert_ext = tls_config.certificate.extension().and_then(|s| s.to_str());
+        // Load the client identity for mutual TLS, if there is one.
+        #[cfg(feature = "websocket-tls-rustls")]
+        let identity = tls_config
+            .identity
+            .as_ref()
+            .map(crate::tls::read_identity)
+            .transpose()
+            .map_err(WebsocketError::TLSError)?;
+
+        // A pinned fingerprint is checked in place of, or in addition to, CA validation.
+        if let Some(fingerprint) = tls_config.pinned_sha256 {
+            #[cfg(feature = "websocket-tls-rustls")]
+            {
+                let root_certificate = tls_config
+                    .certificate
+                    .as_ref()
+                    .map(crate::tls::read_certificate)
+                    .transpose()
+                    .map_err(WebsocketError::TLSError)?;
+
+                let config =
+                    crate::tls::pinned_client_config(fingerprint, root_certificate, identity)
+                        .map_err(|e| {
+                            WebsocketError::TLSError(format!(
+                                "Failed to create pinned TLS config: {e}"
+                            ))
+                        })?;
+                return Ok(Some(tokio_tungstenite::Connector::Rustls(
+                    std::sync::Arc::new(config),
+                )));
+            }
+
+            #[cfg(not(feature = "websocket-tls-rustls"))]
+            {
+                let _ = fingerprint;
+                return Err(WebsocketError::TLSError(
+                    "Certificate pinning requires the 'websocket-tls-rustls' feature".to_string(),
+                ));
+            }
+        }
+
+        if tls_config.danger_accept_invalid_certs {
+            log::warn!("TLS certificate verification is disabled for the WebSocket connection!");
+
+            #[cfg(feature = "websocket-tls-rustls")]
+            {
+                let config = crate::tls::insecure_client_config(identity).map_err(|e| {
+                    WebsocketError::TLSError(format!("Failed to create TLS config: {e}"))
+                })?;
+                return Ok(Some(tokio_tungstenite::Connector::Rustls(
+                    std::sync::Arc::new(config),
+                )));
+            }
+        }
+
+        if tls_config.certificate.is_none()
+            && tls_config.identity.is_none()
+            && !tls_config.danger_accept_invalid_certs
+        {
+            return Ok(None);
+        }
+
+        // Read the root certificate, determining its format from the source and content.
+        let certificate_data = tls_config
+            .certificate
+            .as_ref()
+            .map(|source| {
+                source.read().map_err(|e| {
+                    WebsocketError::TLSError(format!("Failed to read certificate file: {}", e))
+                })
+            })
+            .transpose()?;
 
         #[cfg(feature = "websocket-tls-rustls")]
         {
             let _ = rustls::crypto::CryptoProvider::install_default(
                 rustls::crypto::aws_lc_rs::default_provider(),
             );
-            let certificate = parse_certificate_rustls(&certificate_data, cert_ext)?;
-            create_rustls_connector(certificate)
+            let certificate = certificate_data
+                .map(|(data, ext)| parse_certificate_rustls(&data, ext))
+                .transpose()?;
+            create_rustls_connector(certificate, tls_config.include_default_roots, identity)
         }
 
         #[cfg(feature = "websocket-tls-native")]
         {
-            let certificate = parse_certificate_native(&certificate_data, cert_ext)?;
-            create_native_connector(certificate)
+            let certificate = certificate_data
+                .map(|(data, ext)| parse_certificate_native(&data, ext))
+                .transpose()?;
+            let identity = tls_config
+                .identity
+                .as_ref()
+                .map(load_identity_native)
+                .transpose()?;
+            create_native_connector(
+                certificate,
+                identity,
+                tls_config.danger_accept_invalid_certs,
+            )
         }
     }
 }
@@ -84,18 +164,31 @@ fn parse_pem_rustls(data: &[u8]) -> WebsocketResult<rustls_pki_types::Certificat
         .ok_or_else(|| WebsocketError::TLSError("No certificate found in PEM file".to_string()))
 }
 
+/// Create a rustls connector trusting `certificate`, and the webpki roots if there is no
+/// certificate or they are included by the config.
 #[cfg(feature = "websocket-tls-rustls")]
 fn create_rustls_connector(
-    certificate: rustls_pki_types::CertificateDer<'static>,
+    certificate: Option<rustls_pki_types::CertificateDer<'static>>,
+    include_default_roots: bool,
+    identity: Option<crate::tls::ClientIdentityDer>,
 ) -> WebsocketResult<Option<tokio_tungstenite::Connector>> {
     let mut root_store = rustls::RootCertStore::empty();
-    root_store.add(certificate).map_err(|e| {
-        WebsocketError::TLSError(format!("Failed to add certificate to root store: {}", e))
-    })?;
+    if include_default_roots || certificate.is_none() {
+        root_store.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    }
+    if let Some(certificate) = certificate {
+        root_store.add(certificate).map_err(|e| {
+            WebsocketError::TLSError(format!("Failed to add certificate to root store: {}", e))
+        })?;
+    }
 
-    let tls_config = rustls::ClientConfig::builder()
-        .with_root_certificates(root_store)
-        .with_no_client_auth();
+    let builder = rustls::ClientConfig::builder().with_root_certificates(root_store);
+    let tls_config = match identity {
+        Some((chain, key)) => builder
+            .with_client_auth_cert(chain, key)
+            .map_err(|e| WebsocketError::TLSError(format!("Invalid client identity: {}", e)))?,
+        None => builder.with_no_client_auth(),
+    };
 
     Ok(Some(tokio_tungstenite::Connector::Rustls(
         std::sync::Arc::new(tls_config),
@@ -137,12 +230,37 @@ fn parse_certificate_native(
     }
 }
 
+/// Load a PEM client certificate chain and PKCS#8 key.
+#[cfg(feature = "websocket-tls-native")]
+fn load_identity_native(
+    identity: &crate::config::ClientIdentity,
+) -> WebsocketResult<native_tls::Identity> {
+    let read = |path: &std::path::Path| {
+        std::fs::read(path).map_err(|e| {
+            WebsocketError::TLSError(format!("Failed to read client identity file: {}", e))
+        })
+    };
+
+    native_tls::Identity::from_pkcs8(&read(&identity.certificate)?, &read(&identity.key)?)
+        .map_err(|e| WebsocketError::TLSError(format!("Invalid client identity: {}", e)))
+}
+
 #[cfg(feature = "websocket-tls-native")]
 fn create_native_connector(
-    certificate: native_tls::Certificate,
+    certificate: Option<native_tls::Certificate>,
+    identity: Option<native_tls::Identity>,
+    accept_invalid_certs: bool,
 ) -> WebsocketResult<Option<tokio_tungstenite::Connector>> {
     let mut builder = native_tls::TlsConnector::builder();
-    builder.add_root_certificate(certificate);
+    builder
+        .danger_accept_invalid_certs(accept_invalid_certs)
+        .danger_accept_invalid_hostnames(accept_invalid_certs);
+    if let Some(certificate) = certificate {
+        builder.add_root_certificate(certificate);
+    }
+    if let Some(identity) = identity {
+        builder.identity(identity);
+    }
 
     let tls_connector = builder
         .build()
@@ -7,6 +7,7 @@ use futures_util::{SinkExt, StreamExt};
 pub enum ControlMessage {
     Stop,
     Reconnect,
+    SetFilters(Option<Vec<String>>),
 }
 
 /// Action to take after processing a message
@@ -20,92 +21,242 @@ pub struct WorkerLoop {
     config: crate::config::WebSocketConfig,
     tls_config: Option<crate::config::TLSConfig>,
     callback: Option<crate::ws::MessageCallback>,
+    parse_error_callback: Option<crate::ws::ParseErrorCallback>,
+    unknown_event_callback: Option<crate::ws::UnknownEventCallback>,
     is_connected: std::sync::Arc<tokio::sync::RwLock<bool>>,
+    state: crate::state::StateCache,
+    counters: crate::counters::Counters,
+    debug_dump: Option<crate::dump::DebugDump>,
+    queue: Option<std::sync::Arc<crate::ws::queue::EventQueue>>,
+    connection_state: std::sync::Arc<tokio::sync::watch::Sender<crate::ws::ConnectionState>>,
+
+    /// New event filters to reconnect with, set by a `SetFilters` control message.
+    pending_filters: std::sync::Mutex<Option<Option<Vec<String>>>>,
 }
 impl WorkerLoop {
     /// Create a new worker loop
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         config: crate::config::WebSocketConfig,
         tls_config: Option<crate::config::TLSConfig>,
         callback: Option<crate::ws::MessageCallback>,
+        parse_error_callback: Option<crate::ws::ParseErrorCallback>,
+        unknown_event_callback: Option<crate::ws::UnknownEventCallback>,
         is_connected: std::sync::Arc<tokio::sync::RwLock<bool>>,
+        state: crate::state::StateCache,
+        counters: crate::counters::Counters,
+        debug_dump: Option<crate::dump::DebugDump>,
+        connection_state: std::sync::Arc<tokio::sync::watch::Sender<crate::ws::ConnectionState>>,
     ) -> Self {
+        // A queue is only needed if there is a callback to dispatch to.
+        let queue = config
+            .event_queue
+            .filter(|_| callback.is_some())
+            .map(|queue| std::sync::Arc::new(crate::ws::queue::EventQueue::new(queue)));
+
         Self {
             config,
             tls_config,
             callback,
+            parse_error_callback,
+            unknown_event_callback,
             is_connected,
+            state,
+            counters,
+            debug_dump,
+            queue,
+            connection_state,
+            pending_filters: std::sync::Mutex::new(None),
         }
     }
 
-    /// Run the worker loop
+    /// Run the worker loop, then wait for any queued events to be handled.
     pub async fn run(
         self,
-        mut control_rx: tokio::sync::mpsc::UnboundedReceiver<ControlMessage>,
+        control_rx: tokio::sync::mpsc::UnboundedReceiver<ControlMessage>,
     ) -> WebsocketResult<()> {
+        // Dispatch queued events in a separate task, which finishes the queue once the loop ends.
+        let dispatcher = match (&self.queue, &self.callback) {
+            (Some(queue), Some(callback)) => Some((
+                crate::ws::queue::CloseOnDrop(std::sync::Arc::clone(queue)),
+                tokio::spawn(crate::ws::queue::run_dispatcher(
+                    std::sync::Arc::clone(queue),
+                    std::sync::Arc::clone(callback),
+                )),
+            )),
+            _ => None,
+        };
+
+        let connection_state = std::sync::Arc::clone(&self.connection_state);
+
+        #[cfg(feature = "tracing")]
+        let result = {
+            use tracing::Instrument;
+            let span = tracing::info_span!("sms_client.websocket", url = %self.config.url);
+            self.run_loop(control_rx).instrument(span).await
+        };
+
+        #[cfg(not(feature = "tracing"))]
+        let result = self.run_loop(control_rx).await;
+        if let Some((guard, handle)) = dispatcher {
+            drop(guard);
+            let _ = handle.await;
+        }
+
+        let reason = match &result {
+            Ok(reason) => reason.clone(),
+            Err(WebsocketError::Unauthorized) => crate::ws::StopReason::Unauthorized,
+            Err(e) => crate::ws::StopReason::Error(e.to_string()),
+        };
+        connection_state.send_replace(crate::ws::ConnectionState::Stopped { reason });
+        result.map(|_| ())
+    }
+
+    /// Connect, handle messages and reconnect until stopped.
+    async fn run_loop(
+        mut self,
+        mut control_rx: tokio::sync::mpsc::UnboundedReceiver<ControlMessage>,
+    ) -> WebsocketResult<crate::ws::StopReason> {
         let mut reconnect_count = 0u32;
+        self.connection_state
+            .send_replace(crate::ws::ConnectionState::Connecting);
 
         // Create connection parameters
-        let connection_params = crate::ws::connection::ConnectionParams::from_config(
+        let mut connection_params = crate::ws::connection::ConnectionParams::from_config(
             &self.config,
             self.tls_config.as_ref(),
         )?;
 
-        loop {
+        // Spread out initial connections from clients that started together.
+        if let Some(max_jitter) = self.config.connect_spread() {
+            let delay = tokio::time::sleep(random_jitter(max_jitter));
+            tokio::pin!(delay);
+            loop {
+                tokio::select! {
+                    () = &mut delay => break,
+                    Some(msg) = control_rx.recv() => match msg {
+                        ControlMessage::Stop => {
+                            log::debug!("WebSocket worker stopped during initial connect delay.");
+                            return Ok(crate::ws::StopReason::Requested);
+                        }
+
+                        // Connect now, or keep waiting with the new filters stored.
+                        ControlMessage::Reconnect => break,
+                        ControlMessage::SetFilters(filters) => {
+                            *self.lock_pending_filters() = Some(filters);
+                        }
+                    }
+                }
+            }
+        }
+
+        let reason = loop {
+            // Apply new event filters to the connection query-string.
+            if let Some(filters) = self.take_pending_filters() {
+                self.config.filtered_events = filters;
+                connection_params = crate::ws::connection::ConnectionParams::from_config(
+                    &self.config,
+                    self.tls_config.as_ref(),
+                )?;
+            }
+
             // Try to establish connection and handle messages
             match self
                 .handle_connection(&connection_params, &mut control_rx)
                 .await
             {
                 Ok(should_reconnect) => {
+                    // Reconnect immediately if the event filters changed.
+                    if self.has_pending_filters() {
+                        *self.is_connected.write().await = false;
+                        self.connection_state
+                            .send_replace(crate::ws::ConnectionState::Connecting);
+                        self.emit_connection_update(false, true).await;
+                        continue;
+                    }
+
                     // Emit disconnection event
                     let will_reconnect = should_reconnect && self.config.auto_reconnect;
-                    self.emit_connection_update(false, will_reconnect);
+                    self.emit_connection_update(false, will_reconnect).await;
 
                     if !will_reconnect {
-                        break;
+                        // The connection only ends without a reconnect when a stop was requested.
+                        break if should_reconnect {
+                            crate::ws::StopReason::Disconnected
+                        } else {
+                            crate::ws::StopReason::Requested
+                        };
                     }
                     reconnect_count += 1;
+                    self.counters.record_reconnect();
                 }
                 Err(e) => {
+                    self.counters.record_connection_error(&e);
                     if matches!(e, WebsocketError::Unauthorized) {
                         return Err(e);
                     }
 
                     // Emit disconnection event
                     let will_reconnect = self.config.auto_reconnect;
-                    self.emit_connection_update(false, will_reconnect);
+                    self.emit_connection_update(false, will_reconnect).await;
 
                     log::error!("WebSocket error: {:#?}", e);
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %e, "WebSocket connection failed");
                     if !will_reconnect {
-                        break;
+                        break crate::ws::StopReason::Error(e.to_string());
                     }
                     reconnect_count += 1;
+                    self.counters.record_reconnect();
                 }
             }
 
             *self.is_connected.write().await = false;
+            self.connection_state
+                .send_replace(crate::ws::ConnectionState::Reconnecting {
+                    attempt: reconnect_count,
+                });
 
-            // Backoff delay (capped at 60 seconds)
-            let delay = std::cmp::min(
-                self.config.reconnect_interval * reconnect_count,
-                std::time::Duration::from_secs(60),
-            );
+            // Backoff delay (capped by the config), with optional jitter.
+            let jitter = self
+                .config
+                .connect_spread()
+                .map(random_jitter)
+                .unwrap_or_default();
+            let delay = self.config.reconnect_backoff.delay(
+                self.config.reconnect_interval,
+                reconnect_count,
+                self.config.max_reconnect_delay,
+            ) + jitter;
 
-            // Wait before reconnecting, but check for stop signal
+            // Wait before reconnecting, but check for control messages
             log::debug!("Reconnecting in {:?}...", delay);
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                attempt = reconnect_count,
+                delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                "WebSocket reconnecting"
+            );
             tokio::select! {
                 _ = tokio::time::sleep(delay) => {},
-                Some(ControlMessage::Stop) = control_rx.recv() => {
-                    log::debug!("WebSocket worker stopped during reconnect delay.");
-                    break;
+                Some(msg) = control_rx.recv() => match msg {
+                    ControlMessage::Stop => {
+                        log::debug!("WebSocket worker stopped during reconnect delay.");
+                        break crate::ws::StopReason::Requested;
+                    }
+
+                    // Reconnect now, with the new filters if they were changed.
+                    ControlMessage::Reconnect => {}
+                    ControlMessage::SetFilters(filters) => {
+                        *self.lock_pending_filters() = Some(filters);
+                    }
                 }
             }
-        }
+        };
 
         *self.is_connected.write().await = false;
         log::debug!("WebSocket worker terminated");
-        Ok(())
+        Ok(reason)
     }
 
     /// Handle an active WebSocket connection
@@ -115,10 +266,14 @@ impl WorkerLoop {
         control_rx: &mut tokio::sync::mpsc::UnboundedReceiver<ControlMessage>,
     ) -> WebsocketResult<bool> {
         // Establish connection
-        let ws_stream = connection_params.connect().await?;
+        let ws_stream = connection_params.connect(self.debug_dump.as_ref()).await?;
+        #[cfg(feature = "tracing")]
+        tracing::info!("WebSocket connected");
 
         *self.is_connected.write().await = true;
-        self.emit_connection_update(true, false);
+        self.connection_state
+            .send_replace(crate::ws::ConnectionState::Connected);
+        self.emit_connection_update(true, false).await;
 
         // Set up ping interval
         let mut ping_interval = tokio::time::interval(self.config.ping_interval);
@@ -139,7 +294,7 @@ impl WorkerLoop {
 
                 _ = ping_interval.tick() => {
                     if self.should_send_ping(waiting_for_pong, last_pong_time).await? {
-                        if write.send(tungstenite::Message::Ping(Vec::new().into())).await.is_err() {
+                        if self.send_frame(&mut write, tungstenite::Message::Ping(Vec::new().into())).await.is_err() {
                             log::trace!("Failed to send ping");
                             return Ok(true);
                         }
@@ -169,18 +324,28 @@ impl WorkerLoop {
         last_pong_time: &mut tokio::time::Instant,
         waiting_for_pong: &mut bool,
     ) -> WebsocketResult<MessageAction> {
+        if let (Some(dump), Ok(message)) = (&self.debug_dump, &msg) {
+            dump.record_ws_frame(false, message);
+        }
+
         match msg {
             Ok(tungstenite::Message::Text(text)) => {
-                self.process_text_message(text.to_string());
+                self.process_text_message(text.to_string()).await;
                 Ok(MessageAction::Continue)
             }
             Ok(tungstenite::Message::Close(frame)) => {
                 log::debug!("WebSocket closed by server: {:?}", frame);
+                #[cfg(feature = "tracing")]
+                tracing::info!(frame = ?frame, "WebSocket closed by server");
                 Ok(MessageAction::Reconnect)
             }
             Ok(tungstenite::Message::Ping(data)) => {
                 log::trace!("Received WebSocket ping, sending pong");
-                if write.send(tungstenite::Message::Pong(data)).await.is_err() {
+                if self
+                    .send_frame(write, tungstenite::Message::Pong(data))
+                    .await
+                    .is_err()
+                {
                     return Err(WebsocketError::SendError);
                 }
                 Ok(MessageAction::Continue)
@@ -189,6 +354,8 @@ impl WorkerLoop {
                 *last_pong_time = tokio::time::Instant::now();
                 *waiting_for_pong = false;
                 log::trace!("Received native WebSocket pong frame");
+                #[cfg(feature = "tracing")]
+                tracing::trace!("WebSocket pong received");
                 Ok(MessageAction::Continue)
             }
             Err(e) => {
@@ -200,15 +367,27 @@ impl WorkerLoop {
     }
 
     /// Process text message
-    fn process_text_message(&self, text: String) {
-        match serde_json::from_str::<sms_types::events::Event>(&text) {
+    async fn process_text_message(&self, text: String) {
+        match crate::json::from_slice::<sms_types::events::Event>(text.as_bytes()) {
             Ok(ws_msg) => {
-                if let Some(cb) = &self.callback {
-                    cb(ws_msg);
-                }
+                self.counters.record_event(&ws_msg);
+                self.update_state(&ws_msg);
+                self.dispatch(ws_msg).await;
             }
             Err(e) => {
+                if let Some(callback) = &self.unknown_event_callback
+                    && let Some(event) = crate::ws::UnknownEvent::from_slice(text.as_bytes())
+                {
+                    log::debug!("Unknown WebSocket event '{}': {:#?}", event.event_type, e);
+                    callback(event);
+                    return;
+                }
+
                 log::warn!("Invalid WebSocket message: {:?} -> {:#?}", text, e);
+                self.counters.record_parse_error(&e);
+                if let Some(callback) = &self.parse_error_callback {
+                    callback(&text, &e);
+                }
             }
         }
     }
@@ -226,14 +405,39 @@ impl WorkerLoop {
                     "Ping timeout - no pong received for {:?}",
                     time_since_last_pong
                 );
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    since_pong_ms =
+                        u64::try_from(time_since_last_pong.as_millis()).unwrap_or(u64::MAX),
+                    "WebSocket ping timeout"
+                );
                 return Ok(false);
             }
         }
 
         log::trace!("Sending ping");
+        #[cfg(feature = "tracing")]
+        tracing::trace!("WebSocket ping sent");
         Ok(true)
     }
 
+    /// Send a frame, recording it to the debug dump if set.
+    async fn send_frame(
+        &self,
+        write: &mut futures_util::stream::SplitSink<
+            tokio_tungstenite::WebSocketStream<
+                tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+            >,
+            tungstenite::Message,
+        >,
+        message: tungstenite::Message,
+    ) -> Result<(), tungstenite::Error> {
+        if let Some(dump) = &self.debug_dump {
+            dump.record_ws_frame(true, &message);
+        }
+        write.send(message).await
+    }
+
     /// Handle control messages
     async fn handle_control_message(
         &self,
@@ -248,24 +452,96 @@ impl WorkerLoop {
         match msg {
             ControlMessage::Stop => {
                 log::trace!("Received stop signal");
-                let _ = write.send(tungstenite::Message::Close(None)).await;
+                let _ = self
+                    .send_frame(write, tungstenite::Message::Close(None))
+                    .await;
                 Ok(false)
             }
             ControlMessage::Reconnect => {
                 log::trace!("Received reconnect signal");
-                let _ = write.send(tungstenite::Message::Close(None)).await;
+                let _ = self
+                    .send_frame(write, tungstenite::Message::Close(None))
+                    .await;
+                Ok(true)
+            }
+            ControlMessage::SetFilters(filters) => {
+                log::trace!("Received new event filters, reconnecting");
+                *self.lock_pending_filters() = Some(filters);
+                let _ = self
+                    .send_frame(write, tungstenite::Message::Close(None))
+                    .await;
                 Ok(true)
             }
         }
     }
 
+    /// Take new event filters, if there are any.
+    fn take_pending_filters(&self) -> Option<Option<Vec<String>>> {
+        self.lock_pending_filters().take()
+    }
+
+    /// Check if there are new event filters waiting to be applied.
+    fn has_pending_filters(&self) -> bool {
+        self.lock_pending_filters().is_some()
+    }
+
+    fn lock_pending_filters(&self) -> std::sync::MutexGuard<'_, Option<Option<Vec<String>>>> {
+        match self.pending_filters.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Update the last-known state from a received event. Events take a new revision,
+    /// so they replace values from HTTP requests that were sent before they arrived.
+    fn update_state(&self, event: &sms_types::events::Event) {
+        match event {
+            sms_types::events::Event::ModemStatusUpdate { current, .. } => {
+                self.state.update_newer(
+                    self.state.next_revision(),
+                    |state| &mut state.modem_status,
+                    current.clone(),
+                );
+            }
+            sms_types::events::Event::GNSSPositionReport(report) => {
+                self.state.update_newer(
+                    self.state.next_revision(),
+                    |state| &mut state.gnss_location,
+                    report.clone(),
+                );
+            }
+            _ => {}
+        }
+    }
+
     /// Emit connection status update
-    fn emit_connection_update(&self, connected: bool, reconnect: bool) {
-        if let Some(cb) = &self.callback {
-            cb(sms_types::events::Event::WebsocketConnectionUpdate {
-                connected,
-                reconnect,
-            });
+    async fn emit_connection_update(&self, connected: bool, reconnect: bool) {
+        self.state.update(|state| {
+            state.websocket_connected = Some(crate::state::Timestamped::now(connected));
+        });
+
+        self.dispatch(sms_types::events::Event::WebsocketConnectionUpdate {
+            connected,
+            reconnect,
+        })
+        .await;
+    }
+
+    /// Pass an event to the callback, through the event queue if there is one.
+    async fn dispatch(&self, event: sms_types::events::Event) {
+        if let Some(queue) = &self.queue {
+            queue.push(event).await;
+        } else if let Some(cb) = &self.callback {
+            cb(event);
         }
     }
 }
+
+/// Get a random duration between zero and `max`, seeded from the std hasher's random keys.
+#[allow(clippy::cast_precision_loss)]
+fn random_jitter(max: std::time::Duration) -> std::time::Duration {
+    use std::hash::BuildHasher;
+
+    let random = std::collections::hash_map::RandomState::new().hash_one(std::time::Instant::now());
+    max.mul_f64(random as f64 / u64::MAX as f64)
+}